@@ -0,0 +1,101 @@
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::hint::black_box;
+
+#[cfg(target_arch = "x86_64")]
+use simd_poc::DenormalsFlushed;
+use simd_poc::{
+    MeanFn, calculate_mean_chunks, calculate_mean_chunks_n, calculate_mean_scalar, calculate_mean_simd,
+};
+
+const SIZES: [usize; 5] = [500, 1000, 50000, 1000000, 100000000];
+
+/// Chunk widths swept by the `chunk_width` group
+const CHUNK_WIDTHS: [(usize, MeanFn); 4] = [
+    (4, calculate_mean_chunks_n::<4, f32>),
+    (8, calculate_mean_chunks_n::<8, f32>),
+    (16, calculate_mean_chunks_n::<16, f32>),
+    (32, calculate_mean_chunks_n::<32, f32>),
+];
+
+fn bench_mean(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut group = c.benchmark_group("mean");
+
+    for &size in &SIZES {
+        let data: Vec<f32> = (0..size)
+            .map(|_| rng.gen_range(20.0..100.0))
+            .collect();
+
+        // Large inputs take tens of milliseconds per call, so keep the run bounded
+        group.sample_size(if size >= 1_000_000 { 10 } else { 100 });
+        group.throughput(Throughput::Elements(size as u64));
+
+        group.bench_with_input(BenchmarkId::new("scalar", size), &data, |b, data| {
+            b.iter(|| calculate_mean_scalar(black_box(data)))
+        });
+        group.bench_with_input(BenchmarkId::new("simd", size), &data, |b, data| {
+            b.iter(|| calculate_mean_simd(black_box(data)))
+        });
+        group.bench_with_input(BenchmarkId::new("chunks", size), &data, |b, data| {
+            b.iter(|| calculate_mean_chunks(black_box(data)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_chunk_width(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut group = c.benchmark_group("chunk_width");
+
+    // One cache-resident and one memory-bound size
+    for &size in &[50000, 1000000] {
+        let data: Vec<f32> = (0..size)
+            .map(|_| rng.gen_range(20.0..100.0))
+            .collect();
+
+        group.throughput(Throughput::Elements(size as u64));
+
+        for &(width, mean) in &CHUNK_WIDTHS {
+            group.bench_with_input(BenchmarkId::new(format!("chunks_{}", width), size), &data, |b, data| {
+                b.iter(|| mean(black_box(data)))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+/// Denormal-heavy data with and without flush-to-zero
+#[cfg(target_arch = "x86_64")]
+fn bench_denormals(c: &mut Criterion) {
+    const SIZE: usize = 1000000;
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut group = c.benchmark_group("denormals");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(SIZE as u64));
+
+    // Every element and every partial sum stays below f32::MIN_POSITIVE
+    let data: Vec<f32> = (0..SIZE)
+        .map(|_| rng.gen_range(1.0e-45..1.0e-44))
+        .collect();
+
+    group.bench_function("simd", |b| {
+        b.iter(|| calculate_mean_simd(black_box(&data)))
+    });
+    group.bench_function("simd_flushed", |b| {
+        let _guard = DenormalsFlushed::new();
+        b.iter(|| calculate_mean_simd(black_box(&data)))
+    });
+
+    group.finish();
+}
+
+#[cfg(target_arch = "x86_64")]
+criterion_group!(benches, bench_mean, bench_chunk_width, bench_denormals);
+#[cfg(not(target_arch = "x86_64"))]
+criterion_group!(benches, bench_mean, bench_chunk_width);
+criterion_main!(benches);
@@ -0,0 +1,149 @@
+//! Non-inlined, unmangled wrappers around each mean implementation for reading the generated assembly.
+//!
+//! Each wrapper gets its own symbol, so the loop the compiler produced can be
+//! inspected with `cargo asm` or `objdump`. For example, to see whether
+//! `calculate_mean_chunks` auto-vectorizes for the host CPU:
+//!
+//! ```text
+//! RUSTFLAGS="-C target-cpu=native" cargo build --release --example asm
+//! objdump -d --no-show-raw-insn target/release/examples/asm | less   # search for asm_mean_chunks
+//! RUSTFLAGS="-C target-cpu=native" cargo asm --example asm asm_mean_chunks
+//! ```
+
+use std::hint::black_box;
+
+use simd_poc::{
+    calculate_mean_chunks, calculate_mean_chunks_n, calculate_mean_kahan, calculate_mean_pairwise,
+    calculate_mean_scalar, calculate_mean_scalar_wide, calculate_mean_simd, calculate_mean_simd_wide,
+};
+
+#[cfg(target_arch = "x86_64")]
+use simd_poc::{
+    calculate_mean_simd_avx, calculate_mean_simd_avx512, calculate_mean_simd_avx_single_acc,
+    calculate_mean_simd_sse,
+};
+
+#[inline(never)]
+#[unsafe(no_mangle)]
+pub fn asm_mean_scalar(data: &[f32]) -> f32 {
+    calculate_mean_scalar(data)
+}
+
+#[inline(never)]
+#[unsafe(no_mangle)]
+pub fn asm_mean_scalar_wide(data: &[f32]) -> f32 {
+    calculate_mean_scalar_wide(data)
+}
+
+#[inline(never)]
+#[unsafe(no_mangle)]
+pub fn asm_mean_kahan(data: &[f32]) -> f32 {
+    calculate_mean_kahan(data)
+}
+
+#[inline(never)]
+#[unsafe(no_mangle)]
+pub fn asm_mean_pairwise(data: &[f32]) -> f32 {
+    calculate_mean_pairwise(data)
+}
+
+#[inline(never)]
+#[unsafe(no_mangle)]
+pub fn asm_mean_chunks(data: &[f32]) -> f32 {
+    calculate_mean_chunks(data)
+}
+
+#[inline(never)]
+#[unsafe(no_mangle)]
+pub fn asm_mean_chunks_16(data: &[f32]) -> f32 {
+    calculate_mean_chunks_n::<16, f32>(data)
+}
+
+#[inline(never)]
+#[unsafe(no_mangle)]
+pub fn asm_mean_simd(data: &[f32]) -> f32 {
+    calculate_mean_simd(data)
+}
+
+#[inline(never)]
+#[unsafe(no_mangle)]
+pub fn asm_mean_simd_wide(data: &[f32]) -> f32 {
+    calculate_mean_simd_wide(data)
+}
+
+// The kernels below are `#[target_feature]` functions, so their wrappers enable
+// the same feature; otherwise the kernel could not be inlined into them and the
+// symbol would only show a call.
+
+/// # Safety
+///
+/// The CPU must support sse2.
+#[cfg(target_arch = "x86_64")]
+#[inline(never)]
+#[unsafe(no_mangle)]
+#[target_feature(enable = "sse2")]
+pub unsafe fn asm_mean_sse(data: &[f32]) -> f32 {
+    unsafe { calculate_mean_simd_sse(data) }
+}
+
+/// # Safety
+///
+/// The CPU must support avx.
+#[cfg(target_arch = "x86_64")]
+#[inline(never)]
+#[unsafe(no_mangle)]
+#[target_feature(enable = "avx")]
+pub unsafe fn asm_mean_avx(data: &[f32]) -> f32 {
+    unsafe { calculate_mean_simd_avx(data) }
+}
+
+/// # Safety
+///
+/// The CPU must support avx.
+#[cfg(target_arch = "x86_64")]
+#[inline(never)]
+#[unsafe(no_mangle)]
+#[target_feature(enable = "avx")]
+pub unsafe fn asm_mean_avx_single_acc(data: &[f32]) -> f32 {
+    unsafe { calculate_mean_simd_avx_single_acc(data) }
+}
+
+/// # Safety
+///
+/// The CPU must support avx512f.
+#[cfg(target_arch = "x86_64")]
+#[inline(never)]
+#[unsafe(no_mangle)]
+#[target_feature(enable = "avx512f")]
+pub unsafe fn asm_mean_avx512(data: &[f32]) -> f32 {
+    unsafe { calculate_mean_simd_avx512(data) }
+}
+
+fn main() {
+    // Call every wrapper through black_box so none of them is dropped or folded into main
+    let data: Vec<f32> = (0..1000).map(|i| i as f32).collect();
+    let data = black_box(data.as_slice());
+
+    println!("scalar:      {}", asm_mean_scalar(data));
+    println!("scalar_wide: {}", asm_mean_scalar_wide(data));
+    println!("kahan:       {}", asm_mean_kahan(data));
+    println!("pairwise:    {}", asm_mean_pairwise(data));
+    println!("chunks:      {}", asm_mean_chunks(data));
+    println!("chunks_16:   {}", asm_mean_chunks_16(data));
+    println!("simd:        {}", asm_mean_simd(data));
+    println!("simd_wide:   {}", asm_mean_simd_wide(data));
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("sse2") {
+            println!("sse:         {}", unsafe { asm_mean_sse(data) });
+        }
+        if is_x86_feature_detected!("avx") {
+            println!("avx:         {}", unsafe { asm_mean_avx(data) });
+            println!("avx_single:  {}", unsafe { asm_mean_avx_single_acc(data) });
+        }
+        if is_x86_feature_detected!("avx512f") {
+            println!("avx512:      {}", unsafe { asm_mean_avx512(data) });
+        }
+    }
+}
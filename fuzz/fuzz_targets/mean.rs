@@ -0,0 +1,44 @@
+//! Checks the SIMD mean against the scalar mean for arbitrary slices.
+//!
+//! Every input length exercises a different split between the vector loop and
+//! the scalar remainder, which is where the SIMD paths are most error-prone.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use simd_poc::{calculate_mean_scalar, calculate_mean_simd};
+
+fuzz_target!(|bytes: &[u8]| {
+    let data: Vec<f32> = bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+
+    // NaN and infinity propagate differently depending on summation order
+    if data.is_empty() || data.iter().any(|x| !x.is_finite()) {
+        return;
+    }
+
+    // Skip inputs where some summation order could overflow
+    let abs_sum: f64 = data.iter().map(|&x| x.abs() as f64).sum();
+    if abs_sum > f32::MAX as f64 {
+        return;
+    }
+
+    let scalar = calculate_mean_scalar(&data);
+    let simd = calculate_mean_simd(&data);
+
+    // Each summation order is off by at most about n * eps * sum(|x|), and
+    // dividing by n leaves n * eps * mean(|x|) for the mean
+    let n = data.len() as f64;
+    let tolerance = 2.0 * n * f32::EPSILON as f64 * (abs_sum / n) + f32::MIN_POSITIVE as f64;
+    assert!(
+        ((simd as f64) - (scalar as f64)).abs() <= tolerance,
+        "len {}: simd {} vs scalar {} (tolerance {})",
+        data.len(),
+        simd,
+        scalar,
+        tolerance
+    );
+});
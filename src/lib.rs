@@ -0,0 +1,3574 @@
+//! SIMD and scalar implementations of mean and related reductions over float slices.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// Without `std`, or with `force-scalar`, feature checks are compile-time
+// constants, so dispatch chains can end up comparing the same literal twice
+#![cfg_attr(any(not(feature = "std"), feature = "force-scalar"), allow(clippy::ifs_same_cond))]
+#![cfg_attr(feature = "portable-simd", feature(portable_simd))]
+
+extern crate alloc;
+
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+#[cfg(target_arch = "aarch64")]
+use core::arch::aarch64::*;
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+use core::arch::wasm32::*;
+
+#[cfg(feature = "portable-simd")]
+use core::simd::{f32x8, num::SimdFloat};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "ndarray")]
+use ndarray::{Array1, ArrayView2};
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use num_traits::Float;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::Sum;
+#[cfg(target_arch = "x86_64")]
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+/// Check for x86 CPU features at runtime, or at compile time without `std`
+///
+/// `is_x86_feature_detected!` lives in `std`, so `no_std` builds only take the
+/// paths enabled by `-C target-feature` (or `-C target-cpu`) and otherwise
+/// fall back to the scalar or SSE2 kernels. The `force-scalar` feature reports
+/// every feature as missing, so only the scalar fallbacks are reachable.
+#[cfg(all(target_arch = "x86_64", feature = "std", not(feature = "force-scalar")))]
+macro_rules! has_feature {
+    ($($feature:tt),+) => {
+        $(std::is_x86_feature_detected!($feature))&&+
+    };
+}
+
+#[cfg(all(target_arch = "x86_64", not(feature = "std"), not(feature = "force-scalar")))]
+macro_rules! has_feature {
+    ($($feature:tt),+) => {
+        cfg!(all($(target_feature = $feature),+))
+    };
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "force-scalar"))]
+macro_rules! has_feature {
+    ($($feature:tt),+) => {
+        false
+    };
+}
+
+/// Error returned when two slices that are combined element-wise differ in length
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthMismatch {
+    pub left: usize,
+    pub right: usize,
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "slice lengths differ: {} vs {}", self.left, self.right)
+    }
+}
+
+impl core::error::Error for LengthMismatch {}
+
+/// Error returned when a sliding-window function is given a window of zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroWindow;
+
+impl fmt::Display for ZeroWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "window size must be non-zero")
+    }
+}
+
+impl core::error::Error for ZeroWindow {}
+
+/// Error returned when a smoothing factor lies outside `0.0 < alpha <= 1.0`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvalidAlpha(pub f32);
+
+impl fmt::Display for InvalidAlpha {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "alpha must be in (0, 1], got {}", self.0)
+    }
+}
+
+impl core::error::Error for InvalidAlpha {}
+
+/// Error returned when an index list refers past the end of the data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexOutOfBounds {
+    pub index: u32,
+    pub len: usize,
+}
+
+impl fmt::Display for IndexOutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "index {} out of bounds for length {}", self.index, self.len)
+    }
+}
+
+impl core::error::Error for IndexOutOfBounds {}
+
+/// Mean using scalar operations, or `None` for an empty slice
+pub fn mean_scalar(data: &[f32]) -> Option<f32> {
+    (!data.is_empty()).then(|| calculate_mean_scalar(data))
+}
+
+/// Mean using SIMD operations, or `None` for an empty slice
+pub fn mean_simd(data: &[f32]) -> Option<f32> {
+    (!data.is_empty()).then(|| calculate_mean_simd(data))
+}
+
+/// Mean using the chunked approach, or `None` for an empty slice
+pub fn mean_chunks(data: &[f32]) -> Option<f32> {
+    (!data.is_empty()).then(|| calculate_mean_chunks(data))
+}
+
+/// Mean of the items of an iterator, or `None` if it yields nothing
+///
+/// Sum and count are accumulated in one scalar pass; SIMD needs a contiguous
+/// slice, so prefer [`mean_simd`] when the data is already in one.
+pub fn mean_iter<I: IntoIterator<Item = f32>>(iter: I) -> Option<f32> {
+    let (sum, count) = iter
+        .into_iter()
+        .fold((0.0f32, 0usize), |(sum, count), x| (sum + x, count + 1));
+    (count > 0).then(|| sum / count as f32)
+}
+
+/// Calculate mean using scalar operations
+pub fn calculate_mean_scalar(data: &[f32]) -> f32 {
+    let sum: f32 = data.iter().sum();
+    sum / data.len() as f32
+}
+
+/// Calculate mean ignoring NaN elements, or `None` if every element is NaN
+///
+/// Only NaNs are skipped; infinities are kept and propagate into the mean.
+#[cfg(target_arch = "x86_64")]
+pub fn calculate_mean_skipnan(data: &[f32]) -> Option<f32> {
+    let (sum, count) = if has_feature!("avx") {
+        unsafe { sum_count_skipnan_avx(data) }
+    } else {
+        sum_count_skipnan_scalar(data)
+    };
+    (count > 0).then(|| sum / count as f32)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn calculate_mean_skipnan(data: &[f32]) -> Option<f32> {
+    let (sum, count) = sum_count_skipnan_scalar(data);
+    (count > 0).then(|| sum / count as f32)
+}
+
+fn sum_count_skipnan_scalar(data: &[f32]) -> (f32, usize) {
+    data.iter()
+        .filter(|x| !x.is_nan())
+        .fold((0.0, 0), |(sum, count), &x| (sum + x, count + 1))
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn sum_count_skipnan_avx(data: &[f32]) -> (f32, usize) {
+    let mut sum = _mm256_setzero_ps();
+    let mut count = 0usize;
+    let mut i = 0;
+
+    // Process 8 floats at a time, zeroing NaN lanes before accumulating
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
+            let ordered = _mm256_cmp_ps(chunk, chunk, _CMP_ORD_Q);
+            sum = _mm256_add_ps(sum, _mm256_and_ps(ordered, chunk));
+            count += (_mm256_movemask_ps(ordered) as u32).count_ones() as usize;
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    let (remaining_sum, remaining_count) = sum_count_skipnan_scalar(&data[i..]);
+
+    unsafe { (hsum256_ps(sum) + remaining_sum, count + remaining_count) }
+}
+
+/// Calculate mean using Kahan-Babuška-Neumaier compensated summation
+///
+/// Tracks the rounding error lost by each addition in a separate compensation
+/// term, so large arrays don't drift the way the naive running sum does.
+pub fn calculate_mean_kahan(data: &[f32]) -> f32 {
+    let mut sum = 0.0f32;
+    let mut compensation = 0.0f32;
+
+    for &x in data {
+        let t = sum + x;
+        if sum.abs() >= x.abs() {
+            compensation += (sum - t) + x;
+        } else {
+            compensation += (x - t) + sum;
+        }
+        sum = t;
+    }
+
+    (sum + compensation) / data.len() as f32
+}
+
+/// Calculate mean using pairwise (cascade) summation
+///
+/// Halves the slice recursively down to blocks of at most 128 elements, sums
+/// each block linearly and adds the halves back up pairwise. Rounding error then
+/// grows with `log n` rather than `n`, at close to naive speed.
+pub fn calculate_mean_pairwise(data: &[f32]) -> f32 {
+    pairwise_sum(data) / data.len() as f32
+}
+
+fn pairwise_sum(data: &[f32]) -> f32 {
+    const BLOCK: usize = 128;
+
+    if data.len() > BLOCK {
+        let (left, right) = data.split_at(data.len() / 2);
+        return pairwise_sum(left) + pairwise_sum(right);
+    }
+
+    // Eight independent lanes let the compiler vectorize the block
+    let mut lanes = [0.0f32; 8];
+    let chunks = data.chunks_exact(8);
+    let remainder = chunks.remainder();
+    for chunk in chunks {
+        for (lane, &x) in lanes.iter_mut().zip(chunk) {
+            *lane += x;
+        }
+    }
+    lanes.iter().sum::<f32>() + remainder.iter().sum::<f32>()
+}
+
+/// Calculate mean using scalar operations with an f64 accumulator
+pub fn calculate_mean_scalar_wide(data: &[f32]) -> f32 {
+    (sum_f64acc_scalar(data) / data.len() as f64) as f32
+}
+
+/// Calculate mean using SIMD operations with f64 accumulators (AVX on x86_64)
+///
+/// The accurate counterpart of [`calculate_mean_simd`]: divides
+/// [`simd_sum_f64acc`] by the length and rounds to f32 only at the end.
+pub fn calculate_mean_simd_wide(data: &[f32]) -> f32 {
+    (simd_sum_f64acc(data) / data.len() as f64) as f32
+}
+
+/// Sum of a slice accumulated in f64 using SIMD operations (AVX on x86_64)
+///
+/// Each 8-float chunk is widened to two 4-double vectors before adding, so the
+/// total keeps its low-order bits even where a running f32 sum would drop
+/// them, e.g. 100M values near 100.0.
+#[cfg(target_arch = "x86_64")]
+pub fn simd_sum_f64acc(data: &[f32]) -> f64 {
+    if has_feature!("avx") {
+        unsafe { sum_f64acc_avx(data) }
+    } else {
+        sum_f64acc_scalar(data)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_sum_f64acc(data: &[f32]) -> f64 {
+    sum_f64acc_scalar(data)
+}
+
+fn sum_f64acc_scalar(data: &[f32]) -> f64 {
+    data.iter().fold(0.0f64, |acc, &x| acc + x as f64)
+}
+
+/// Calculate mean using SIMD operations (AVX-512, AVX or SSE2 on x86_64)
+///
+/// Dispatches through [`best_mean_impl`], so feature detection only runs once.
+#[cfg(target_arch = "x86_64")]
+pub fn calculate_mean_simd(data: &[f32]) -> f32 {
+    best_mean_impl()(data)
+}
+
+/// Mean via [`calculate_mean_simd`], checked against a reference in debug builds
+///
+/// With `debug_assertions` enabled this also computes the mean with an f64
+/// accumulator and panics if the SIMD result differs from it by more than
+/// `n * f32::EPSILON * mean(|x|)`, the worst-case rounding error of any f32
+/// summation order. Equal results, including matching infinities, and both
+/// results being NaN count as agreement. In release builds it compiles to a
+/// plain [`calculate_mean_simd`] call.
+#[cfg(debug_assertions)]
+pub fn checked_mean(data: &[f32]) -> f32 {
+    let simd = calculate_mean_simd(data);
+    let reference = calculate_mean_scalar_wide(data);
+    let n = data.len() as f32;
+    let scale = data.iter().map(|x| x.abs()).sum::<f32>() / n;
+    let tolerance = n * f32::EPSILON * scale;
+    debug_assert!(
+        simd == reference
+            || (simd.is_nan() && reference.is_nan())
+            || (simd - reference).abs() <= tolerance,
+        "SIMD mean {} differs from reference {} by more than {} (len {})",
+        simd,
+        reference,
+        tolerance,
+        data.len()
+    );
+    simd
+}
+
+#[cfg(not(debug_assertions))]
+#[inline]
+pub fn checked_mean(data: &[f32]) -> f32 {
+    calculate_mean_simd(data)
+}
+
+/// Signature shared by the mean implementations
+pub type MeanFn = fn(&[f32]) -> f32;
+
+/// Return the fastest mean implementation available on this CPU
+///
+/// Feature detection runs on the first call and the result is cached, so the
+/// returned pointer can be stored and called in hot loops without re-checking.
+#[cfg(feature = "std")]
+pub fn best_mean_impl() -> MeanFn {
+    static BEST: OnceLock<MeanFn> = OnceLock::new();
+    *BEST.get_or_init(detect_mean_impl)
+}
+
+/// Return the fastest mean implementation enabled at compile time
+///
+/// Without `std` there is no runtime detection, so nothing needs caching.
+#[cfg(not(feature = "std"))]
+pub fn best_mean_impl() -> MeanFn {
+    detect_mean_impl()
+}
+
+#[cfg(target_arch = "x86_64")]
+fn detect_mean_impl() -> MeanFn {
+    // Each closure is only handed out after its feature has been detected
+    if has_feature!("avx512f") {
+        |data| unsafe { calculate_mean_simd_avx512(data) }
+    } else if has_feature!("avx") {
+        |data| unsafe { calculate_mean_simd_avx(data) }
+    } else if has_feature!("sse2") {
+        |data| unsafe { calculate_mean_simd_sse(data) }
+    } else {
+        calculate_mean_scalar
+    }
+}
+
+// Elsewhere `calculate_mean_simd` already resolves to the best kernel at compile time
+#[cfg(not(target_arch = "x86_64"))]
+fn detect_mean_impl() -> MeanFn {
+    calculate_mean_simd
+}
+
+/// The SIMD features the dispatch checks, paired with whether each is available
+///
+/// Reports exactly what [`best_mean_impl`] and the other entry points see, so
+/// it explains which kernel a machine picked: runtime detection on x86_64 with
+/// `std`, the compile-time target features otherwise, and nothing at all under
+/// `force-scalar`.
+#[cfg(target_arch = "x86_64")]
+pub fn report_cpu_features() -> Vec<(&'static str, bool)> {
+    vec![
+        ("sse2", has_feature!("sse2")),
+        ("avx", has_feature!("avx")),
+        ("avx2", has_feature!("avx2")),
+        ("fma", has_feature!("fma")),
+        ("avx512f", has_feature!("avx512f")),
+    ]
+}
+
+#[cfg(target_arch = "aarch64")]
+pub fn report_cpu_features() -> Vec<(&'static str, bool)> {
+    vec![("neon", cfg!(all(target_feature = "neon", not(feature = "force-scalar"))))]
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn report_cpu_features() -> Vec<(&'static str, bool)> {
+    vec![("simd128", cfg!(all(target_feature = "simd128", not(feature = "force-scalar"))))]
+}
+
+#[cfg(target_arch = "riscv64")]
+pub fn report_cpu_features() -> Vec<(&'static str, bool)> {
+    vec![("v", cfg!(all(target_feature = "v", not(feature = "force-scalar"))))]
+}
+
+#[cfg(not(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    target_arch = "wasm32",
+    target_arch = "riscv64"
+)))]
+pub fn report_cpu_features() -> Vec<(&'static str, bool)> {
+    Vec::new()
+}
+
+/// Sum of a slice using SIMD operations
+pub fn simd_sum(data: &[f32]) -> f32 {
+    simd_sum_count(data).0
+}
+
+/// Sum and element count of a slice using SIMD operations
+///
+/// The shared primitive behind the SIMD means: divide the sum by the count for
+/// the mean, or add pairs field by field to combine partial results.
+#[cfg(target_arch = "x86_64")]
+pub fn simd_sum_count(data: &[f32]) -> (f32, usize) {
+    if has_feature!("avx") {
+        unsafe { sum_count_avx(data) }
+    } else if has_feature!("sse2") {
+        (unsafe { sum_sse2(data) }, data.len())
+    } else {
+        (data.iter().sum(), data.len())
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_sum_count(data: &[f32]) -> (f32, usize) {
+    let sum = if cfg!(feature = "force-scalar") {
+        data.iter().sum()
+    } else {
+        vector_sum(data)
+    };
+    (sum, data.len())
+}
+
+#[cfg(target_arch = "aarch64")]
+fn vector_sum(data: &[f32]) -> f32 {
+    unsafe { sum_neon(data) }
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+fn vector_sum(data: &[f32]) -> f32 {
+    sum_wasm(data)
+}
+
+#[cfg(all(target_arch = "riscv64", target_feature = "v"))]
+fn vector_sum(data: &[f32]) -> f32 {
+    sum_rvv(data)
+}
+
+#[cfg(not(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128"),
+    all(target_arch = "riscv64", target_feature = "v")
+)))]
+fn vector_sum(data: &[f32]) -> f32 {
+    // The 8-lane blocks of the pairwise sum are what auto-vectorizes best here
+    pairwise_sum(data)
+}
+
+/// A summing backend of a fixed SIMD width
+///
+/// The methods take `&self` so a backend chosen at runtime can be held as a
+/// `Box<dyn SimdReduce>` (see [`detect_backend`]), while generic code can still
+/// name a concrete backend to force it.
+pub trait SimdReduce {
+    /// Sum of all elements
+    fn sum(&self, data: &[f32]) -> f32;
+
+    /// Number of f32 lanes processed per instruction
+    fn width(&self) -> usize;
+}
+
+/// Plain scalar loop, available everywhere
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScalarBackend;
+
+impl SimdReduce for ScalarBackend {
+    fn sum(&self, data: &[f32]) -> f32 {
+        data.iter().sum()
+    }
+
+    fn width(&self) -> usize {
+        1
+    }
+}
+
+/// 8-lane AVX backend; construct with [`AvxBackend::new`]
+#[cfg(target_arch = "x86_64")]
+#[derive(Debug, Clone, Copy)]
+pub struct AvxBackend {
+    _detected: (),
+}
+
+#[cfg(target_arch = "x86_64")]
+impl AvxBackend {
+    /// The AVX backend, or `None` if the CPU lacks avx
+    pub fn new() -> Option<Self> {
+        has_feature!("avx").then_some(AvxBackend { _detected: () })
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl SimdReduce for AvxBackend {
+    fn sum(&self, data: &[f32]) -> f32 {
+        // Safety: `new` only succeeds once avx has been detected
+        unsafe { sum_avx_impl::<false>(data) }
+    }
+
+    fn width(&self) -> usize {
+        8
+    }
+}
+
+/// 4-lane SSE2 backend; construct with [`Sse2Backend::new`]
+#[cfg(target_arch = "x86_64")]
+#[derive(Debug, Clone, Copy)]
+pub struct Sse2Backend {
+    _detected: (),
+}
+
+#[cfg(target_arch = "x86_64")]
+impl Sse2Backend {
+    /// The SSE2 backend, or `None` if the CPU lacks sse2
+    pub fn new() -> Option<Self> {
+        has_feature!("sse2").then_some(Sse2Backend { _detected: () })
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl SimdReduce for Sse2Backend {
+    fn sum(&self, data: &[f32]) -> f32 {
+        // Safety: `new` only succeeds once sse2 has been detected
+        unsafe { sum_sse2(data) }
+    }
+
+    fn width(&self) -> usize {
+        4
+    }
+}
+
+/// Return the widest [`SimdReduce`] backend this CPU supports
+#[cfg(target_arch = "x86_64")]
+pub fn detect_backend() -> Box<dyn SimdReduce> {
+    if let Some(avx) = AvxBackend::new() {
+        Box::new(avx)
+    } else if let Some(sse2) = Sse2Backend::new() {
+        Box::new(sse2)
+    } else {
+        Box::new(ScalarBackend)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn detect_backend() -> Box<dyn SimdReduce> {
+    Box::new(ScalarBackend)
+}
+
+/// Calculate mean using SIMD operations (NEON on aarch64)
+#[cfg(target_arch = "aarch64")]
+pub fn calculate_mean_simd(data: &[f32]) -> f32 {
+    if cfg!(feature = "force-scalar") {
+        return calculate_mean_scalar(data);
+    }
+    // NEON is part of the aarch64 baseline, so no runtime detection is needed
+    unsafe { calculate_mean_simd_neon(data) }
+}
+
+/// Calculate mean using SIMD operations (SIMD128 on wasm32)
+///
+/// WebAssembly has no runtime feature detection, so this path is chosen at
+/// compile time; build with `RUSTFLAGS="-C target-feature=+simd128"`.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+pub fn calculate_mean_simd(data: &[f32]) -> f32 {
+    if cfg!(feature = "force-scalar") {
+        return calculate_mean_scalar(data);
+    }
+    calculate_mean_simd_wasm(data)
+}
+
+/// Calculate mean using SIMD operations (the V extension on riscv64)
+///
+/// Like wasm32 this is chosen at compile time; build with
+/// `RUSTFLAGS="-C target-feature=+v"`.
+#[cfg(all(target_arch = "riscv64", target_feature = "v"))]
+pub fn calculate_mean_simd(data: &[f32]) -> f32 {
+    if cfg!(feature = "force-scalar") {
+        return calculate_mean_scalar(data);
+    }
+    calculate_mean_simd_rvv(data)
+}
+
+#[cfg(not(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128"),
+    all(target_arch = "riscv64", target_feature = "v")
+)))]
+pub fn calculate_mean_simd(data: &[f32]) -> f32 {
+    if cfg!(feature = "force-scalar") {
+        return calculate_mean_scalar(data);
+    }
+    calculate_mean_chunks(data)
+}
+
+/// Calculate mean using four AVX accumulators
+///
+/// Uses aligned loads when `data` starts on a 32-byte boundary and unaligned
+/// loads otherwise.
+///
+/// # Safety
+///
+/// The CPU must support avx; check with `is_x86_feature_detected!("avx")`.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+pub unsafe fn calculate_mean_simd_avx(data: &[f32]) -> f32 {
+    let (sum, count) = unsafe { sum_count_avx(data) };
+    sum / count as f32
+}
+
+/// AVX kernel behind [`simd_sum_count`], picking aligned loads when it can
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn sum_count_avx(data: &[f32]) -> (f32, usize) {
+    let sum = if is_aligned_32(data) {
+        unsafe { sum_avx_impl::<true>(data) }
+    } else {
+        unsafe { sum_avx_impl::<false>(data) }
+    };
+    (sum, data.len())
+}
+
+/// Calculate mean using four AVX accumulators and aligned loads
+///
+/// # Safety
+///
+/// The CPU must support avx and `data` must start on a 32-byte boundary
+/// (see [`is_aligned_32`] and [`aligned_vec_f32`]).
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+pub unsafe fn calculate_mean_simd_avx_aligned(data: &[f32]) -> f32 {
+    debug_assert!(is_aligned_32(data));
+    unsafe { calculate_mean_simd_avx_impl::<true>(data) }
+}
+
+/// Calculate mean using four AVX accumulators and unaligned loads
+///
+/// # Safety
+///
+/// The CPU must support avx; check with `is_x86_feature_detected!("avx")`.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+pub unsafe fn calculate_mean_simd_avx_unaligned(data: &[f32]) -> f32 {
+    unsafe { calculate_mean_simd_avx_impl::<false>(data) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn calculate_mean_simd_avx_impl<const ALIGNED: bool>(data: &[f32]) -> f32 {
+    unsafe { sum_avx_impl::<ALIGNED>(data) / data.len() as f32 }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn sum_avx_impl<const ALIGNED: bool>(data: &[f32]) -> f32 {
+    // Every offset is a multiple of 8 floats, so an aligned start keeps all loads aligned
+    macro_rules! load {
+        ($ptr:expr) => {
+            if ALIGNED { _mm256_load_ps($ptr) } else { _mm256_loadu_ps($ptr) }
+        };
+    }
+
+    // Four independent accumulators so consecutive adds don't wait on each other
+    let mut sum0 = _mm256_setzero_ps();
+    let mut sum1 = _mm256_setzero_ps();
+    let mut sum2 = _mm256_setzero_ps();
+    let mut sum3 = _mm256_setzero_ps();
+    let mut i = 0;
+
+    // Process 32 floats at a time using AVX
+    while i + 32 <= data.len() {
+        unsafe {
+            let ptr = data.as_ptr().add(i);
+            sum0 = _mm256_add_ps(sum0, load!(ptr));
+            sum1 = _mm256_add_ps(sum1, load!(ptr.add(8)));
+            sum2 = _mm256_add_ps(sum2, load!(ptr.add(16)));
+            sum3 = _mm256_add_ps(sum3, load!(ptr.add(24)));
+        }
+        i += 32;
+    }
+
+    // Process any remaining full 8-float chunks
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = load!(data.as_ptr().add(i));
+            sum0 = _mm256_add_ps(sum0, chunk);
+        }
+        i += 8;
+    }
+
+    let sum = _mm256_add_ps(_mm256_add_ps(sum0, sum1), _mm256_add_ps(sum2, sum3));
+
+    // Fold to 128 bits so a 4-7 float tail still gets one SSE add instead of going scalar
+    let mut sum = _mm_add_ps(_mm256_castps256_ps128(sum), _mm256_extractf128_ps(sum, 1));
+    if i + 4 <= data.len() {
+        unsafe {
+            sum = _mm_add_ps(sum, _mm_loadu_ps(data.as_ptr().add(i)));
+        }
+        i += 4;
+    }
+
+    let simd_sum = unsafe { hsum128_ps(sum) };
+
+    // Handle remaining elements
+    let remaining_sum: f32 = data[i..].iter().sum();
+
+    simd_sum + remaining_sum
+}
+
+/// Horizontal sum of the 8 lanes of an AVX register
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+#[inline]
+unsafe fn hsum256_ps(v: __m256) -> f32 {
+    // Fold the upper 128-bit half onto the lower one, then sum the 4 lanes left
+    let low = _mm256_castps256_ps128(v);
+    let high = _mm256_extractf128_ps(v, 1);
+    unsafe { hsum128_ps(_mm_add_ps(low, high)) }
+}
+
+/// Horizontal sum of the 4 lanes of an SSE register
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+#[inline]
+unsafe fn hsum128_ps(v: __m128) -> f32 {
+    // Add adjacent pairs twice
+    let sum = _mm_hadd_ps(v, v);
+    let sum = _mm_hadd_ps(sum, sum);
+    _mm_cvtss_f32(sum)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn sum_f64acc_avx(data: &[f32]) -> f64 {
+    let mut sum_low = _mm256_setzero_pd();
+    let mut sum_high = _mm256_setzero_pd();
+    let mut i = 0;
+
+    // Process 8 floats at a time, widening each 128-bit half to 4 doubles
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
+            let low = _mm256_cvtps_pd(_mm256_castps256_ps128(chunk));
+            let high = _mm256_cvtps_pd(_mm256_extractf128_ps(chunk, 1));
+            sum_low = _mm256_add_pd(sum_low, low);
+            sum_high = _mm256_add_pd(sum_high, high);
+        }
+        i += 8;
+    }
+
+    let simd_sum = unsafe { hsum256_pd(_mm256_add_pd(sum_low, sum_high)) };
+
+    // Handle remaining elements
+    let remaining_sum = sum_f64acc_scalar(&data[i..]);
+
+    simd_sum + remaining_sum
+}
+
+/// Horizontal sum of the 4 lanes of an AVX double-precision register
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+#[inline]
+unsafe fn hsum256_pd(v: __m256d) -> f64 {
+    let low = _mm256_castpd256_pd128(v);
+    let high = _mm256_extractf128_pd(v, 1);
+    let sum = _mm_add_pd(low, high);
+    let sum = _mm_hadd_pd(sum, sum);
+    _mm_cvtsd_f64(sum)
+}
+
+/// Single-accumulator AVX loop, kept as a baseline for the unrolled version
+///
+/// # Safety
+///
+/// The CPU must support avx; check with `is_x86_feature_detected!("avx")`.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+pub unsafe fn calculate_mean_simd_avx_single_acc(data: &[f32]) -> f32 {
+    let sum = unsafe {
+        simd_reduce_ps(
+            data,
+            _mm256_setzero_ps(),
+            |chunk, sum| _mm256_add_ps(chunk, sum),
+            |sum| hsum256_ps(sum),
+            |sum, x| sum + x,
+        )
+    };
+    sum / data.len() as f32
+}
+
+/// Single-accumulator AVX reduction shared by the sum, min and max kernels
+///
+/// Combines each 8-float chunk into `init` with `combine(chunk, acc)`, folds
+/// the lanes with `hreduce`, then folds in the 0-7 remaining elements with
+/// `scalar_combine`. The chunk is always the first operand, which the min and
+/// max kernels rely on to skip NaNs.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+#[inline]
+unsafe fn simd_reduce_ps(
+    data: &[f32],
+    init: __m256,
+    combine: impl Fn(__m256, __m256) -> __m256,
+    hreduce: impl Fn(__m256) -> f32,
+    scalar_combine: impl Fn(f32, f32) -> f32,
+) -> f32 {
+    let mut acc = init;
+    let mut i = 0;
+
+    // Process 8 floats at a time using AVX
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
+            acc = combine(chunk, acc);
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    data[i..].iter().fold(hreduce(acc), |acc, &x| scalar_combine(acc, x))
+}
+
+/// The 8 lanes of an AVX register, lowest first
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+#[inline]
+unsafe fn lanes256_ps(v: __m256) -> [f32; 8] {
+    let mut lanes = [0.0f32; 8];
+    unsafe {
+        _mm256_storeu_ps(lanes.as_mut_ptr(), v);
+    }
+    lanes
+}
+
+/// Calculate mean using AVX-512
+///
+/// # Safety
+///
+/// The CPU must support avx512f; check with `is_x86_feature_detected!("avx512f")`.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
+pub unsafe fn calculate_mean_simd_avx512(data: &[f32]) -> f32 {
+    let mut sum = _mm512_setzero_ps();
+    let mut i = 0;
+
+    // Process 16 floats at a time using AVX-512
+    while i + 16 <= data.len() {
+        unsafe {
+            let chunk = _mm512_loadu_ps(data.as_ptr().add(i));
+            sum = _mm512_add_ps(sum, chunk);
+        }
+        i += 16;
+    }
+
+    // Load the 0-15 remaining floats with a mask so the tail stays in-register;
+    // masked-off lanes are zeroed and never touch memory past the slice
+    let remaining = data.len() - i;
+    if remaining > 0 {
+        let mask: __mmask16 = (1 << remaining) - 1;
+        unsafe {
+            let chunk = _mm512_maskz_loadu_ps(mask, data.as_ptr().add(i));
+            sum = _mm512_add_ps(sum, chunk);
+        }
+    }
+
+    _mm512_reduce_add_ps(sum) / data.len() as f32
+}
+
+/// Calculate mean using SSE2
+///
+/// # Safety
+///
+/// The CPU must support sse2; check with `is_x86_feature_detected!("sse2")`.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+pub unsafe fn calculate_mean_simd_sse(data: &[f32]) -> f32 {
+    unsafe { sum_sse2(data) / data.len() as f32 }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn sum_sse2(data: &[f32]) -> f32 {
+    let mut sum = _mm_setzero_ps();
+    let mut i = 0;
+
+    // Process 4 floats at a time using SSE
+    while i + 4 <= data.len() {
+        unsafe {
+            let chunk = _mm_loadu_ps(data.as_ptr().add(i));
+            sum = _mm_add_ps(sum, chunk);
+        }
+        i += 4;
+    }
+
+    // Extract the sum from the SSE register
+    let mut result = [0.0f32; 4];
+    unsafe {
+        _mm_storeu_ps(result.as_mut_ptr(), sum);
+    }
+    let simd_sum: f32 = result.iter().sum();
+
+    // Handle remaining elements
+    let remaining_sum: f32 = data[i..].iter().sum();
+
+    simd_sum + remaining_sum
+}
+
+/// Calculate mean using NEON
+///
+/// # Safety
+///
+/// The CPU must support NEON, which every aarch64 target does by default.
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+pub unsafe fn calculate_mean_simd_neon(data: &[f32]) -> f32 {
+    unsafe { sum_neon(data) / data.len() as f32 }
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn sum_neon(data: &[f32]) -> f32 {
+    // Four independent accumulators so consecutive adds don't wait on each other
+    let mut sum0 = vdupq_n_f32(0.0);
+    let mut sum1 = vdupq_n_f32(0.0);
+    let mut sum2 = vdupq_n_f32(0.0);
+    let mut sum3 = vdupq_n_f32(0.0);
+    let mut i = 0;
+
+    // Process 16 floats at a time using NEON
+    while i + 16 <= data.len() {
+        unsafe {
+            let ptr = data.as_ptr().add(i);
+            sum0 = vaddq_f32(sum0, vld1q_f32(ptr));
+            sum1 = vaddq_f32(sum1, vld1q_f32(ptr.add(4)));
+            sum2 = vaddq_f32(sum2, vld1q_f32(ptr.add(8)));
+            sum3 = vaddq_f32(sum3, vld1q_f32(ptr.add(12)));
+        }
+        i += 16;
+    }
+
+    // Process any remaining full 4-float chunks
+    while i + 4 <= data.len() {
+        unsafe {
+            let chunk = vld1q_f32(data.as_ptr().add(i));
+            sum0 = vaddq_f32(sum0, chunk);
+        }
+        i += 4;
+    }
+
+    let simd_sum = vaddvq_f32(vaddq_f32(vaddq_f32(sum0, sum1), vaddq_f32(sum2, sum3)));
+
+    // Handle remaining elements
+    let remaining_sum: f32 = data[i..].iter().sum();
+
+    simd_sum + remaining_sum
+}
+
+/// Single-accumulator NEON loop, kept as a baseline for the unrolled version
+///
+/// # Safety
+///
+/// The CPU must support NEON, which every aarch64 target does by default.
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+pub unsafe fn calculate_mean_simd_neon_single_acc(data: &[f32]) -> f32 {
+    let mut sum = vdupq_n_f32(0.0);
+    let mut i = 0;
+
+    // Process 4 floats at a time using NEON
+    while i + 4 <= data.len() {
+        unsafe {
+            let chunk = vld1q_f32(data.as_ptr().add(i));
+            sum = vaddq_f32(sum, chunk);
+        }
+        i += 4;
+    }
+
+    let simd_sum = vaddvq_f32(sum);
+
+    // Handle remaining elements
+    let remaining_sum: f32 = data[i..].iter().sum();
+
+    (simd_sum + remaining_sum) / data.len() as f32
+}
+
+/// Calculate mean using the RISC-V Vector extension
+///
+/// `core::arch::riscv64` has no vector intrinsics yet, so the loop is inline
+/// assembly. It is vector-length agnostic: `vsetvli` hands out as many lanes as
+/// the hardware has, or fewer on the last iteration, so there is no separate
+/// remainder loop. The accumulator is tail-undisturbed, keeping the lanes a
+/// short final pass doesn't reach.
+#[cfg(all(target_arch = "riscv64", target_feature = "v"))]
+pub fn calculate_mean_simd_rvv(data: &[f32]) -> f32 {
+    sum_rvv(data) / data.len() as f32
+}
+
+#[cfg(all(target_arch = "riscv64", target_feature = "v"))]
+fn sum_rvv(data: &[f32]) -> f32 {
+    let sum: f32;
+    unsafe {
+        core::arch::asm!(
+            // Zero the whole accumulator group v8-v11
+            "vsetvli {vl}, zero, e32, m4, ta, ma",
+            "vmv.v.i v8, 0",
+            // Process up to VLMAX floats at a time; `vl` is 0 for an empty slice
+            "2:",
+            "vsetvli {vl}, {len}, e32, m4, tu, ma",
+            "vle32.v v16, ({ptr})",
+            "vfadd.vv v8, v8, v16",
+            "sub {len}, {len}, {vl}",
+            "slli {vl}, {vl}, 2",
+            "add {ptr}, {ptr}, {vl}",
+            "bnez {len}, 2b",
+            // Reduce every lane of the accumulator into element 0 of v24
+            "vsetvli {vl}, zero, e32, m4, ta, ma",
+            "vmv.s.x v24, zero",
+            "vfredusum.vs v24, v8, v24",
+            "vfmv.f.s {sum}, v24",
+            len = inout(reg) data.len() => _,
+            ptr = inout(reg) data.as_ptr() => _,
+            vl = out(reg) _,
+            sum = out(freg) sum,
+            out("v8") _, out("v9") _, out("v10") _, out("v11") _,
+            out("v16") _, out("v17") _, out("v18") _, out("v19") _,
+            out("v24") _,
+            options(nostack, readonly),
+        );
+    }
+    sum
+}
+
+/// Calculate mean using WebAssembly SIMD128
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+pub fn calculate_mean_simd_wasm(data: &[f32]) -> f32 {
+    sum_wasm(data) / data.len() as f32
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+fn sum_wasm(data: &[f32]) -> f32 {
+    let mut sum = f32x4_splat(0.0);
+    let mut i = 0;
+
+    // Process 4 floats at a time using SIMD128
+    while i + 4 <= data.len() {
+        unsafe {
+            let chunk = v128_load(data.as_ptr().add(i).cast());
+            sum = f32x4_add(sum, chunk);
+        }
+        i += 4;
+    }
+
+    let simd_sum = f32x4_extract_lane::<0>(sum)
+        + f32x4_extract_lane::<1>(sum)
+        + f32x4_extract_lane::<2>(sum)
+        + f32x4_extract_lane::<3>(sum);
+
+    // Handle remaining elements
+    let remaining_sum: f32 = data[i..].iter().sum();
+
+    simd_sum + remaining_sum
+}
+
+/// Calculate sum and sum of squares in a single pass (FMA or AVX on x86_64)
+#[cfg(target_arch = "x86_64")]
+pub fn calculate_sum_and_sumsq_avx(data: &[f32]) -> (f32, f32) {
+    if has_feature!("avx", "fma") {
+        unsafe { calculate_sum_and_sumsq_avx_fma(data) }
+    } else if has_feature!("avx") {
+        unsafe { calculate_sum_and_sumsq_avx_mul(data) }
+    } else {
+        calculate_sum_and_sumsq_scalar(data)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn calculate_sum_and_sumsq_avx(data: &[f32]) -> (f32, f32) {
+    calculate_sum_and_sumsq_scalar(data)
+}
+
+fn calculate_sum_and_sumsq_scalar(data: &[f32]) -> (f32, f32) {
+    data.iter().fold((0.0, 0.0), |(sum, sumsq), &x| (sum + x, sumsq + x * x))
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx,fma")]
+unsafe fn calculate_sum_and_sumsq_avx_fma(data: &[f32]) -> (f32, f32) {
+    let mut sum = _mm256_setzero_ps();
+    let mut sumsq = _mm256_setzero_ps();
+    let mut i = 0;
+
+    // Process 8 floats at a time, accumulating x and x*x
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
+            sum = _mm256_add_ps(sum, chunk);
+            sumsq = _mm256_fmadd_ps(chunk, chunk, sumsq);
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    let (remaining_sum, remaining_sumsq) = calculate_sum_and_sumsq_scalar(&data[i..]);
+
+    unsafe {
+        (hsum256_ps(sum) + remaining_sum, hsum256_ps(sumsq) + remaining_sumsq)
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn calculate_sum_and_sumsq_avx_mul(data: &[f32]) -> (f32, f32) {
+    let mut sum = _mm256_setzero_ps();
+    let mut sumsq = _mm256_setzero_ps();
+    let mut i = 0;
+
+    // Process 8 floats at a time, accumulating x and x*x
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
+            sum = _mm256_add_ps(sum, chunk);
+            sumsq = _mm256_add_ps(sumsq, _mm256_mul_ps(chunk, chunk));
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    let (remaining_sum, remaining_sumsq) = calculate_sum_and_sumsq_scalar(&data[i..]);
+
+    unsafe {
+        (hsum256_ps(sum) + remaining_sum, hsum256_ps(sumsq) + remaining_sumsq)
+    }
+}
+
+/// Sum `f(x)` over a slice, applying `map` to each 8-float AVX chunk before accumulating
+///
+/// `scalar_map` must compute the same transform for one element; it handles
+/// the 0-7 remaining elements, and the whole slice when AVX isn't available,
+/// in which case `map` is never called. Returns `0.0` for an empty slice.
+#[cfg(target_arch = "x86_64")]
+pub fn simd_map_reduce(
+    data: &[f32],
+    map: impl Fn(__m256) -> __m256,
+    scalar_map: impl Fn(f32) -> f32,
+) -> f32 {
+    if has_feature!("avx") {
+        unsafe { map_reduce_avx(data, map, scalar_map) }
+    } else {
+        data.iter().map(|&x| scalar_map(x)).sum()
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn map_reduce_avx(
+    data: &[f32],
+    map: impl Fn(__m256) -> __m256,
+    scalar_map: impl Fn(f32) -> f32,
+) -> f32 {
+    unsafe {
+        simd_reduce_ps(
+            data,
+            _mm256_setzero_ps(),
+            |chunk, sum| _mm256_add_ps(map(chunk), sum),
+            |sum| hsum256_ps(sum),
+            |sum, x| sum + scalar_map(x),
+        )
+    }
+}
+
+/// Calculate the sum of squares, `0.0` for an empty slice
+#[cfg(target_arch = "x86_64")]
+pub fn simd_sum_squares(data: &[f32]) -> f32 {
+    simd_map_reduce(data, |chunk| unsafe { _mm256_mul_ps(chunk, chunk) }, |x| x * x)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_sum_squares(data: &[f32]) -> f32 {
+    data.iter().map(|&x| x * x).sum()
+}
+
+/// Calculate the sum of absolute values, `0.0` for an empty slice
+#[cfg(target_arch = "x86_64")]
+pub fn simd_sum_abs(data: &[f32]) -> f32 {
+    // Clearing the sign bit is an exact |x|, NaNs included
+    simd_map_reduce(
+        data,
+        |chunk| unsafe { _mm256_andnot_ps(_mm256_set1_ps(-0.0), chunk) },
+        f32::abs,
+    )
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_sum_abs(data: &[f32]) -> f32 {
+    data.iter().map(|&x| x.abs()).sum()
+}
+
+/// Calculate the L1 norm (sum of absolute values), `0.0` for an empty slice
+///
+/// The same reduction as [`simd_sum_abs`]: signs are cleared with
+/// `_mm256_andnot_ps` against a sign-bit mask before accumulating.
+pub fn simd_l1_norm(data: &[f32]) -> f32 {
+    simd_sum_abs(data)
+}
+
+/// Calculate the L2 (Euclidean) norm, `0.0` for an empty slice
+pub fn simd_l2_norm(data: &[f32]) -> f32 {
+    simd_sum_squares(data).sqrt()
+}
+
+/// Calculate the root mean square, or `None` for an empty slice
+pub fn simd_rms(data: &[f32]) -> Option<f32> {
+    if data.is_empty() {
+        return None;
+    }
+    Some((simd_sum_squares(data) / data.len() as f32).sqrt())
+}
+
+/// Calculate the geometric mean, or `None` for an empty slice or any element `<= 0.0`
+///
+/// Computed in log-space as `exp(mean(ln(x)))` so long products can't
+/// overflow. The logarithms are scalar; there is no SIMD `ln` in `std::arch`.
+pub fn geometric_mean(data: &[f32]) -> Option<f32> {
+    if data.is_empty() || data.iter().any(|&x| x <= 0.0) {
+        return None;
+    }
+    let log_sum: f64 = data.iter().map(|&x| (x as f64).ln()).sum();
+    Some((log_sum / data.len() as f64).exp() as f32)
+}
+
+/// Calculate the harmonic mean, or `None` for an empty slice or any zero element
+#[cfg(target_arch = "x86_64")]
+pub fn harmonic_mean(data: &[f32]) -> Option<f32> {
+    if data.is_empty() || data.contains(&0.0) {
+        return None;
+    }
+    let reciprocal_sum = if has_feature!("avx") {
+        unsafe { reciprocal_sum_avx(data) }
+    } else {
+        reciprocal_sum_scalar(data)
+    };
+    Some(data.len() as f32 / reciprocal_sum)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn harmonic_mean(data: &[f32]) -> Option<f32> {
+    if data.is_empty() || data.contains(&0.0) {
+        return None;
+    }
+    Some(data.len() as f32 / reciprocal_sum_scalar(data))
+}
+
+fn reciprocal_sum_scalar(data: &[f32]) -> f32 {
+    data.iter().map(|&x| 1.0 / x).sum()
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn reciprocal_sum_avx(data: &[f32]) -> f32 {
+    // A full-precision divide rather than `_mm256_rcp_ps`, whose ~12-bit
+    // approximation would be visible in the result
+    let ones = _mm256_set1_ps(1.0);
+    let mut sum = _mm256_setzero_ps();
+    let mut i = 0;
+
+    // Process 8 floats at a time, accumulating 1/x
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
+            sum = _mm256_add_ps(sum, _mm256_div_ps(ones, chunk));
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    let remaining_sum = reciprocal_sum_scalar(&data[i..]);
+
+    unsafe { hsum256_ps(sum) + remaining_sum }
+}
+
+/// Calculate population variance in one pass from the sum and sum of squares
+///
+/// Computes `E[x²] - E[x]²`, which cancels catastrophically when the mean is
+/// large relative to the spread; kept to compare against [`variance_two_pass`].
+pub fn variance_one_pass(data: &[f32]) -> f32 {
+    let (sum, sumsq) = calculate_sum_and_sumsq_avx(data);
+    let n = data.len() as f32;
+    let mean = sum / n;
+    sumsq / n - mean * mean
+}
+
+/// Calculate population variance in two passes; the same as [`calculate_variance_simd`]
+pub fn variance_two_pass(data: &[f32]) -> f32 {
+    calculate_variance_simd(data)
+}
+
+/// Calculate population variance using a two-pass SIMD approach
+///
+/// The mean is computed first and the squared deviations from it are summed
+/// in a second pass, which avoids the cancellation of the naive sum-of-squares.
+pub fn calculate_variance_simd(data: &[f32]) -> f32 {
+    let mean = calculate_mean_simd(data);
+    sum_squared_deviations(data, mean) / data.len() as f32
+}
+
+/// Calculate population standard deviation using a two-pass SIMD approach
+pub fn calculate_std_simd(data: &[f32]) -> f32 {
+    calculate_variance_simd(data).sqrt()
+}
+
+#[cfg(target_arch = "x86_64")]
+fn sum_squared_deviations(data: &[f32], mean: f32) -> f32 {
+    if has_feature!("avx") {
+        unsafe { sum_squared_deviations_avx(data, mean) }
+    } else {
+        sum_squared_deviations_scalar(data, mean)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn sum_squared_deviations(data: &[f32], mean: f32) -> f32 {
+    sum_squared_deviations_scalar(data, mean)
+}
+
+fn sum_squared_deviations_scalar(data: &[f32], mean: f32) -> f32 {
+    data.iter().map(|&x| (x - mean) * (x - mean)).sum()
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn sum_squared_deviations_avx(data: &[f32], mean: f32) -> f32 {
+    let mean_vec = _mm256_set1_ps(mean);
+    let mut sum = _mm256_setzero_ps();
+    let mut i = 0;
+
+    // Process 8 floats at a time using AVX
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
+            let diff = _mm256_sub_ps(chunk, mean_vec);
+            sum = _mm256_add_ps(sum, _mm256_mul_ps(diff, diff));
+        }
+        i += 8;
+    }
+
+    let simd_sum = unsafe { hsum256_ps(sum) };
+
+    // Handle remaining elements
+    simd_sum + sum_squared_deviations_scalar(&data[i..], mean)
+}
+
+/// Calculate the population skewness `m3 / m2^1.5`, or `None` for an empty or constant slice
+pub fn simd_skewness(data: &[f32]) -> Option<f32> {
+    let (m2, m3, _) = central_moments(data)?;
+    Some(m3 / (m2 * m2.sqrt()))
+}
+
+/// Calculate the population excess kurtosis `m4 / m2² - 3`, or `None` for an empty or constant slice
+///
+/// A normal distribution has an excess kurtosis of zero.
+pub fn simd_kurtosis(data: &[f32]) -> Option<f32> {
+    let (m2, _, m4) = central_moments(data)?;
+    Some(m4 / (m2 * m2) - 3.0)
+}
+
+/// Second, third and fourth central moments, or `None` when the variance is zero
+fn central_moments(data: &[f32]) -> Option<(f32, f32, f32)> {
+    if data.is_empty() {
+        return None;
+    }
+    let mean = calculate_mean_simd(data);
+    let (sum2, sum3, sum4) = sum_central_powers(data, mean);
+    let n = data.len() as f32;
+    let m2 = sum2 / n;
+    (m2 > 0.0).then(|| (m2, sum3 / n, sum4 / n))
+}
+
+#[cfg(target_arch = "x86_64")]
+fn sum_central_powers(data: &[f32], mean: f32) -> (f32, f32, f32) {
+    if has_feature!("avx") {
+        unsafe { sum_central_powers_avx(data, mean) }
+    } else {
+        sum_central_powers_scalar(data, mean)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn sum_central_powers(data: &[f32], mean: f32) -> (f32, f32, f32) {
+    sum_central_powers_scalar(data, mean)
+}
+
+fn sum_central_powers_scalar(data: &[f32], mean: f32) -> (f32, f32, f32) {
+    data.iter().fold((0.0, 0.0, 0.0), |(sum2, sum3, sum4), &x| {
+        let d = x - mean;
+        let d2 = d * d;
+        (sum2 + d2, sum3 + d2 * d, sum4 + d2 * d2)
+    })
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn sum_central_powers_avx(data: &[f32], mean: f32) -> (f32, f32, f32) {
+    let mean_vec = _mm256_set1_ps(mean);
+    let mut sum2 = _mm256_setzero_ps();
+    let mut sum3 = _mm256_setzero_ps();
+    let mut sum4 = _mm256_setzero_ps();
+    let mut i = 0;
+
+    // Process 8 floats at a time, accumulating d², d³ and d⁴ from one load
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
+            let d = _mm256_sub_ps(chunk, mean_vec);
+            let d2 = _mm256_mul_ps(d, d);
+            sum2 = _mm256_add_ps(sum2, d2);
+            sum3 = _mm256_add_ps(sum3, _mm256_mul_ps(d2, d));
+            sum4 = _mm256_add_ps(sum4, _mm256_mul_ps(d2, d2));
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    let (remaining2, remaining3, remaining4) = sum_central_powers_scalar(&data[i..], mean);
+
+    unsafe {
+        (
+            hsum256_ps(sum2) + remaining2,
+            hsum256_ps(sum3) + remaining3,
+            hsum256_ps(sum4) + remaining4,
+        )
+    }
+}
+
+/// Minimum of a slice using SIMD operations, or `None` for an empty slice
+///
+/// NaNs are ignored, matching `f32::min`; a slice of only NaNs yields `Some(NaN)`.
+#[cfg(target_arch = "x86_64")]
+pub fn simd_min(data: &[f32]) -> Option<f32> {
+    if data.is_empty() {
+        None
+    } else if has_feature!("avx") {
+        Some(unsafe { simd_min_avx(data) })
+    } else {
+        Some(scalar_min(data))
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_min(data: &[f32]) -> Option<f32> {
+    (!data.is_empty()).then(|| scalar_min(data))
+}
+
+/// Maximum of a slice using SIMD operations, or `None` for an empty slice
+///
+/// NaNs are ignored, matching `f32::max`; a slice of only NaNs yields `Some(NaN)`.
+#[cfg(target_arch = "x86_64")]
+pub fn simd_max(data: &[f32]) -> Option<f32> {
+    if data.is_empty() {
+        None
+    } else if has_feature!("avx") {
+        Some(unsafe { simd_max_avx(data) })
+    } else {
+        Some(scalar_max(data))
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_max(data: &[f32]) -> Option<f32> {
+    (!data.is_empty()).then(|| scalar_max(data))
+}
+
+/// Minimum and maximum of a slice in a single SIMD pass, or `None` for an empty slice
+///
+/// Reads the data once instead of twice for separate [`simd_min`] and
+/// [`simd_max`] calls, with the same NaN handling; a slice of only NaNs yields
+/// `Some((NaN, NaN))`.
+#[cfg(target_arch = "x86_64")]
+pub fn simd_min_max(data: &[f32]) -> Option<(f32, f32)> {
+    if data.is_empty() {
+        None
+    } else if has_feature!("avx") {
+        Some(unsafe { simd_min_max_avx(data) })
+    } else {
+        Some(scalar_min_max(data))
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_min_max(data: &[f32]) -> Option<(f32, f32)> {
+    (!data.is_empty()).then(|| scalar_min_max(data))
+}
+
+fn scalar_min_max(data: &[f32]) -> (f32, f32) {
+    data.iter()
+        .fold((f32::NAN, f32::NAN), |(min, max), &x| (min.min(x), max.max(x)))
+}
+
+fn scalar_min(data: &[f32]) -> f32 {
+    data.iter().copied().fold(f32::NAN, f32::min)
+}
+
+fn scalar_max(data: &[f32]) -> f32 {
+    data.iter().copied().fold(f32::NAN, f32::max)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn simd_min_avx(data: &[f32]) -> f32 {
+    // `_mm256_min_ps` returns its second operand when either is NaN, so keeping
+    // the accumulator second skips NaNs
+    let min = unsafe {
+        simd_reduce_ps(
+            data,
+            _mm256_set1_ps(f32::INFINITY),
+            |chunk, min| _mm256_min_ps(chunk, min),
+            |min| lanes256_ps(min).into_iter().fold(f32::INFINITY, f32::min),
+            f32::min,
+        )
+    };
+
+    // The accumulator starts at +inf, so tell an all-NaN slice apart from a real +inf
+    if min == f32::INFINITY && data.iter().all(|x| x.is_nan()) {
+        f32::NAN
+    } else {
+        min
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn simd_max_avx(data: &[f32]) -> f32 {
+    // `_mm256_max_ps` returns its second operand when either is NaN, so keeping
+    // the accumulator second skips NaNs
+    let max = unsafe {
+        simd_reduce_ps(
+            data,
+            _mm256_set1_ps(f32::NEG_INFINITY),
+            |chunk, max| _mm256_max_ps(chunk, max),
+            |max| lanes256_ps(max).into_iter().fold(f32::NEG_INFINITY, f32::max),
+            f32::max,
+        )
+    };
+
+    // The accumulator starts at -inf, so tell an all-NaN slice apart from a real -inf
+    if max == f32::NEG_INFINITY && data.iter().all(|x| x.is_nan()) {
+        f32::NAN
+    } else {
+        max
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn simd_min_max_avx(data: &[f32]) -> (f32, f32) {
+    let mut min = _mm256_set1_ps(f32::INFINITY);
+    let mut max = _mm256_set1_ps(f32::NEG_INFINITY);
+    let mut i = 0;
+
+    // Process 8 floats at a time using AVX, keeping the accumulators second to skip NaNs
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
+            min = _mm256_min_ps(chunk, min);
+            max = _mm256_max_ps(chunk, max);
+        }
+        i += 8;
+    }
+
+    // Reduce the lanes together with the remaining elements
+    let (min_lanes, max_lanes) = unsafe { (lanes256_ps(min), lanes256_ps(max)) };
+    let min = min_lanes.iter().chain(&data[i..]).copied().fold(f32::INFINITY, f32::min);
+    let max = max_lanes.iter().chain(&data[i..]).copied().fold(f32::NEG_INFINITY, f32::max);
+
+    // Both accumulators are untouched only when every element was NaN
+    if min > max {
+        (f32::NAN, f32::NAN)
+    } else {
+        (min, max)
+    }
+}
+
+/// Index of the maximum element, or `None` for an empty or all-NaN slice
+///
+/// NaNs are ignored. Ties always resolve to the lowest index, matching a
+/// scalar `iter().enumerate()` scan whatever order the SIMD lanes are
+/// combined in; `0.0` and `-0.0` count as equal.
+#[cfg(target_arch = "x86_64")]
+pub fn simd_argmax(data: &[f32]) -> Option<usize> {
+    if has_feature!("avx2") && data.len() <= i32::MAX as usize {
+        unsafe { simd_arg_extreme_avx2::<true>(data) }
+    } else {
+        scalar_arg_extreme::<true>(data)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_argmax(data: &[f32]) -> Option<usize> {
+    scalar_arg_extreme::<true>(data)
+}
+
+/// Index of the minimum element, or `None` for an empty or all-NaN slice
+///
+/// NaNs are ignored. Ties always resolve to the lowest index, matching a
+/// scalar `iter().enumerate()` scan whatever order the SIMD lanes are
+/// combined in; `0.0` and `-0.0` count as equal.
+#[cfg(target_arch = "x86_64")]
+pub fn simd_argmin(data: &[f32]) -> Option<usize> {
+    if has_feature!("avx2") && data.len() <= i32::MAX as usize {
+        unsafe { simd_arg_extreme_avx2::<false>(data) }
+    } else {
+        scalar_arg_extreme::<false>(data)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_argmin(data: &[f32]) -> Option<usize> {
+    scalar_arg_extreme::<false>(data)
+}
+
+/// Whether `x` beats `best`: strictly greater for `MAX`, strictly less otherwise
+fn beats<const MAX: bool>(x: f32, best: f32) -> bool {
+    if MAX { x > best } else { x < best }
+}
+
+/// Fold `(index, value)` pairs into the lowest index holding the extreme non-NaN value
+///
+/// Ties are broken by comparing indices rather than by arrival order, so the
+/// pairs may come in any order.
+fn arg_extreme_of<const MAX: bool>(
+    items: impl Iterator<Item = (usize, f32)>,
+) -> Option<(usize, f32)> {
+    items
+        .filter(|(_, x)| !x.is_nan())
+        .fold(None, |best, (i, x)| match best {
+            Some((j, b)) if beats::<MAX>(b, x) || (b == x && j < i) => best,
+            _ => Some((i, x)),
+        })
+}
+
+fn scalar_arg_extreme<const MAX: bool>(data: &[f32]) -> Option<usize> {
+    arg_extreme_of::<MAX>(data.iter().copied().enumerate()).map(|(i, _)| i)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn simd_arg_extreme_avx2<const MAX: bool>(data: &[f32]) -> Option<usize> {
+    let bound = if MAX { f32::NEG_INFINITY } else { f32::INFINITY };
+    let mut best = _mm256_set1_ps(bound);
+    let mut best_idx = _mm256_set1_epi32(-1);
+    let mut idx = _mm256_setr_epi32(0, 1, 2, 3, 4, 5, 6, 7);
+    let step = _mm256_set1_epi32(8);
+    let mut i = 0;
+
+    // Process 8 floats at a time using AVX2. Each lane only moves on a strict
+    // (ordered) improvement, so NaNs never win, and since a lane's indices only
+    // grow, an equal value never replaces the smaller index it already holds.
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
+            let mask = if MAX {
+                _mm256_cmp_ps(chunk, best, _CMP_GT_OQ)
+            } else {
+                _mm256_cmp_ps(chunk, best, _CMP_LT_OQ)
+            };
+            best = _mm256_blendv_ps(best, chunk, mask);
+            best_idx = _mm256_castps_si256(_mm256_blendv_ps(
+                _mm256_castsi256_ps(best_idx),
+                _mm256_castsi256_ps(idx),
+                mask,
+            ));
+            idx = _mm256_add_epi32(idx, step);
+        }
+        i += 8;
+    }
+
+    // Resolve the winning lane together with the remaining elements; ties
+    // between lanes are broken by index
+    let mut values = [0.0f32; 8];
+    let mut indices = [0i32; 8];
+    unsafe {
+        _mm256_storeu_ps(values.as_mut_ptr(), best);
+        _mm256_storeu_si256(indices.as_mut_ptr().cast(), best_idx);
+    }
+
+    // Lanes never move off their +-inf start value, so fall back to a scalar
+    // scan when the extreme is that infinity (or the vector part is all NaN)
+    if i > 0 && indices.iter().all(|&lane_idx| lane_idx < 0) {
+        return scalar_arg_extreme::<MAX>(data);
+    }
+
+    let lanes = indices
+        .into_iter()
+        .zip(values)
+        .filter(|&(lane_idx, _)| lane_idx >= 0)
+        .map(|(lane_idx, x)| (lane_idx as usize, x));
+    let tail = data[i..].iter().copied().enumerate().map(|(j, x)| (i + j, x));
+    arg_extreme_of::<MAX>(lanes.chain(tail)).map(|(j, _)| j)
+}
+
+/// Count the elements strictly greater than `threshold`
+///
+/// NaNs never compare greater, so they are not counted.
+#[cfg(target_arch = "x86_64")]
+pub fn simd_count_gt(data: &[f32], threshold: f32) -> usize {
+    if has_feature!("avx") {
+        unsafe { count_gt_avx(data, threshold) }
+    } else {
+        count_gt_scalar(data, threshold)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_count_gt(data: &[f32], threshold: f32) -> usize {
+    count_gt_scalar(data, threshold)
+}
+
+fn count_gt_scalar(data: &[f32], threshold: f32) -> usize {
+    data.iter().filter(|&&x| x > threshold).count()
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn count_gt_avx(data: &[f32], threshold: f32) -> usize {
+    let threshold_vec = _mm256_set1_ps(threshold);
+    let mut count = 0usize;
+    let mut i = 0;
+
+    // Process 8 floats at a time, popcounting the lanes above the threshold
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
+            let above = _mm256_cmp_ps(chunk, threshold_vec, _CMP_GT_OQ);
+            count += (_mm256_movemask_ps(above) as u32).count_ones() as usize;
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    count + count_gt_scalar(&data[i..], threshold)
+}
+
+/// Mean of the elements strictly greater than `threshold`, or `None` if none qualify
+#[cfg(target_arch = "x86_64")]
+pub fn mean_where_gt(data: &[f32], threshold: f32) -> Option<f32> {
+    let (sum, count) = if has_feature!("avx") {
+        unsafe { sum_count_gt_avx(data, threshold) }
+    } else {
+        sum_count_gt_scalar(data, threshold)
+    };
+    (count > 0).then(|| sum / count as f32)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn mean_where_gt(data: &[f32], threshold: f32) -> Option<f32> {
+    let (sum, count) = sum_count_gt_scalar(data, threshold);
+    (count > 0).then(|| sum / count as f32)
+}
+
+fn sum_count_gt_scalar(data: &[f32], threshold: f32) -> (f32, usize) {
+    data.iter()
+        .filter(|&&x| x > threshold)
+        .fold((0.0, 0), |(sum, count), &x| (sum + x, count + 1))
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn sum_count_gt_avx(data: &[f32], threshold: f32) -> (f32, usize) {
+    let threshold_vec = _mm256_set1_ps(threshold);
+    let mut sum = _mm256_setzero_ps();
+    let mut count = 0usize;
+    let mut i = 0;
+
+    // Process 8 floats at a time, zeroing the lanes at or below the threshold
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
+            let above = _mm256_cmp_ps(chunk, threshold_vec, _CMP_GT_OQ);
+            sum = _mm256_add_ps(sum, _mm256_and_ps(above, chunk));
+            count += (_mm256_movemask_ps(above) as u32).count_ones() as usize;
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    let (remaining_sum, remaining_count) = sum_count_gt_scalar(&data[i..], threshold);
+
+    unsafe { (hsum256_ps(sum) + remaining_sum, count + remaining_count) }
+}
+
+/// Mean after clamping every element to `lo..=hi`, or `None` if `data` is
+/// empty, `lo > hi` or either bound is NaN
+///
+/// Clamps lane by lane while summing, so no clamped copy is allocated. NaN
+/// elements propagate like `f32::clamp`.
+#[cfg(target_arch = "x86_64")]
+pub fn clamped_mean(data: &[f32], lo: f32, hi: f32) -> Option<f32> {
+    if data.is_empty() || lo.is_nan() || hi.is_nan() || lo > hi {
+        return None;
+    }
+    let sum = if has_feature!("avx") {
+        unsafe { clamped_sum_avx(data, lo, hi) }
+    } else {
+        clamped_sum_scalar(data, lo, hi)
+    };
+    Some(sum / data.len() as f32)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn clamped_mean(data: &[f32], lo: f32, hi: f32) -> Option<f32> {
+    if data.is_empty() || lo.is_nan() || hi.is_nan() || lo > hi {
+        return None;
+    }
+    Some(clamped_sum_scalar(data, lo, hi) / data.len() as f32)
+}
+
+fn clamped_sum_scalar(data: &[f32], lo: f32, hi: f32) -> f32 {
+    data.iter().map(|x| x.clamp(lo, hi)).sum()
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn clamped_sum_avx(data: &[f32], lo: f32, hi: f32) -> f32 {
+    let lo_v = _mm256_set1_ps(lo);
+    let hi_v = _mm256_set1_ps(hi);
+    let mut sum = _mm256_setzero_ps();
+    let mut i = 0;
+
+    // Process 8 floats at a time using AVX. min/max return their second operand
+    // when either is NaN, so keeping the data second propagates NaNs.
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
+            let clamped = _mm256_min_ps(hi_v, _mm256_max_ps(lo_v, chunk));
+            sum = _mm256_add_ps(sum, clamped);
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    let remaining_sum = clamped_sum_scalar(&data[i..], lo, hi);
+
+    unsafe { hsum256_ps(sum) + remaining_sum }
+}
+
+/// Mean of `data[offset]`, `data[offset + stride]`, ..., or `None` if `stride`
+/// is zero or `offset` is out of bounds
+///
+/// A stride of 1 takes the contiguous SIMD path; wider strides use AVX2
+/// gathers when available.
+#[cfg(target_arch = "x86_64")]
+pub fn mean_strided(data: &[f32], stride: usize, offset: usize) -> Option<f32> {
+    if stride == 0 || offset >= data.len() {
+        return None;
+    }
+    let data = &data[offset..];
+    if stride == 1 {
+        return mean_simd(data);
+    }
+
+    let count = (data.len() - 1) / stride + 1;
+    // Gather offsets are i32, and the last lane sits 7 strides past the base
+    let sum = if has_feature!("avx2") && stride <= i32::MAX as usize / 8 {
+        unsafe { strided_sum_avx2(data, stride, count) }
+    } else {
+        strided_sum_scalar(data, stride)
+    };
+    Some(sum / count as f32)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn mean_strided(data: &[f32], stride: usize, offset: usize) -> Option<f32> {
+    if stride == 0 || offset >= data.len() {
+        return None;
+    }
+    let data = &data[offset..];
+    if stride == 1 {
+        return mean_simd(data);
+    }
+
+    let count = (data.len() - 1) / stride + 1;
+    Some(strided_sum_scalar(data, stride) / count as f32)
+}
+
+fn strided_sum_scalar(data: &[f32], stride: usize) -> f32 {
+    data.iter().step_by(stride).sum()
+}
+
+/// Sum of the first `count` elements of `data` taken `stride` apart
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn strided_sum_avx2(data: &[f32], stride: usize, count: usize) -> f32 {
+    let step = stride as i32;
+    let offsets = _mm256_mullo_epi32(
+        _mm256_setr_epi32(0, 1, 2, 3, 4, 5, 6, 7),
+        _mm256_set1_epi32(step),
+    );
+    let mut sum = _mm256_setzero_ps();
+    let mut j = 0;
+
+    // Gather 8 floats at a time using AVX2
+    while j + 8 <= count {
+        unsafe {
+            let chunk = _mm256_i32gather_ps(data.as_ptr().add(j * stride), offsets, 4);
+            sum = _mm256_add_ps(sum, chunk);
+        }
+        j += 8;
+    }
+
+    // Handle remaining elements (`j * stride` may already be past the end)
+    let remaining_sum: f32 = data.iter().skip(j * stride).step_by(stride).sum();
+
+    unsafe { hsum256_ps(sum) + remaining_sum }
+}
+
+/// Mean of `data[i]` for each `i` in `indices`, or `None` for an empty index list
+///
+/// Every index is bounds-checked up front, so an out-of-range one is reported
+/// before anything is loaded. With AVX2 the elements are gathered 8 at a time.
+#[cfg(target_arch = "x86_64")]
+pub fn mean_indexed(data: &[f32], indices: &[u32]) -> Result<Option<f32>, IndexOutOfBounds> {
+    check_indices(data, indices)?;
+    if indices.is_empty() {
+        return Ok(None);
+    }
+
+    // Gather offsets are i32, so every valid index must fit in one
+    let sum = if has_feature!("avx2") && data.len() <= i32::MAX as usize + 1 {
+        unsafe { indexed_sum_avx2(data, indices) }
+    } else {
+        indexed_sum_scalar(data, indices)
+    };
+    Ok(Some(sum / indices.len() as f32))
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn mean_indexed(data: &[f32], indices: &[u32]) -> Result<Option<f32>, IndexOutOfBounds> {
+    check_indices(data, indices)?;
+    if indices.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(indexed_sum_scalar(data, indices) / indices.len() as f32))
+}
+
+fn check_indices(data: &[f32], indices: &[u32]) -> Result<(), IndexOutOfBounds> {
+    match indices.iter().find(|&&index| index as usize >= data.len()) {
+        Some(&index) => Err(IndexOutOfBounds { index, len: data.len() }),
+        None => Ok(()),
+    }
+}
+
+fn indexed_sum_scalar(data: &[f32], indices: &[u32]) -> f32 {
+    indices.iter().map(|&index| data[index as usize]).sum()
+}
+
+/// Sum of `data[i]` over `indices`, which must all be in bounds and fit in an i32
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn indexed_sum_avx2(data: &[f32], indices: &[u32]) -> f32 {
+    let mut sum = _mm256_setzero_ps();
+    let mut i = 0;
+
+    // Gather 8 floats at a time using AVX2
+    while i + 8 <= indices.len() {
+        unsafe {
+            let offsets = _mm256_loadu_si256(indices.as_ptr().add(i) as *const __m256i);
+            let chunk = _mm256_i32gather_ps(data.as_ptr(), offsets, 4);
+            sum = _mm256_add_ps(sum, chunk);
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    let remaining_sum = indexed_sum_scalar(data, &indices[i..]);
+
+    unsafe { hsum256_ps(sum) + remaining_sum }
+}
+
+/// Multiply every element by `factor` in place
+#[cfg(target_arch = "x86_64")]
+pub fn simd_scale_inplace(data: &mut [f32], factor: f32) {
+    if has_feature!("avx") {
+        unsafe { scale_inplace_avx(data, factor) }
+    } else {
+        scale_inplace_scalar(data, factor)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_scale_inplace(data: &mut [f32], factor: f32) {
+    scale_inplace_scalar(data, factor)
+}
+
+/// Divide every element by the slice's maximum in place
+///
+/// A slice that is empty, all NaN, or whose maximum is zero is left untouched.
+#[cfg(target_arch = "x86_64")]
+pub fn simd_normalize_inplace(data: &mut [f32]) {
+    let Some(max) = simd_max(data).filter(|&max| max != 0.0 && !max.is_nan()) else {
+        return;
+    };
+    if has_feature!("avx") {
+        unsafe { divide_inplace_avx(data, max) }
+    } else {
+        divide_inplace_scalar(data, max)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_normalize_inplace(data: &mut [f32]) {
+    if let Some(max) = simd_max(data).filter(|&max| max != 0.0 && !max.is_nan()) {
+        divide_inplace_scalar(data, max)
+    }
+}
+
+fn scale_inplace_scalar(data: &mut [f32], factor: f32) {
+    data.iter_mut().for_each(|x| *x *= factor);
+}
+
+fn divide_inplace_scalar(data: &mut [f32], divisor: f32) {
+    data.iter_mut().for_each(|x| *x /= divisor);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn scale_inplace_avx(data: &mut [f32], factor: f32) {
+    let factor_vec = _mm256_set1_ps(factor);
+    let mut i = 0;
+
+    // Process 8 floats at a time using AVX
+    while i + 8 <= data.len() {
+        unsafe {
+            let ptr = data.as_mut_ptr().add(i);
+            _mm256_storeu_ps(ptr, _mm256_mul_ps(_mm256_loadu_ps(ptr), factor_vec));
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    scale_inplace_scalar(&mut data[i..], factor);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn divide_inplace_avx(data: &mut [f32], divisor: f32) {
+    let divisor_vec = _mm256_set1_ps(divisor);
+    let mut i = 0;
+
+    // Process 8 floats at a time using AVX
+    while i + 8 <= data.len() {
+        unsafe {
+            let ptr = data.as_mut_ptr().add(i);
+            _mm256_storeu_ps(ptr, _mm256_div_ps(_mm256_loadu_ps(ptr), divisor_vec));
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    divide_inplace_scalar(&mut data[i..], divisor);
+}
+
+/// The `q`-quantile (`0.0..=1.0`) by linear interpolation, or `None` if `data` is empty or `q` is out of range
+///
+/// Partitions `data` in place with `select_nth_unstable_by` instead of sorting
+/// it, so the slice is left reordered. NaNs are ordered by `f32::total_cmp`,
+/// which places positive NaNs above `+inf` and negative NaNs below `-inf`.
+pub fn percentile(data: &mut [f32], q: f64) -> Option<f32> {
+    if data.is_empty() || !(0.0..=1.0).contains(&q) {
+        return None;
+    }
+    let pos = q * (data.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let frac = (pos - lo as f64) as f32;
+
+    let (_, &mut lo_value, above) = data.select_nth_unstable_by(lo, f32::total_cmp);
+    if frac == 0.0 {
+        return Some(lo_value);
+    }
+    // The next order statistic is the smallest element above the partition point
+    let hi_value = above.iter().copied().min_by(f32::total_cmp)?;
+    if hi_value == lo_value {
+        return Some(lo_value);
+    }
+    // Weighting each end, rather than adding a fraction of `hi - lo`, cannot
+    // overflow for neighbours of opposite sign near `f32::MAX`
+    Some(lo_value * (1.0 - frac) + hi_value * frac)
+}
+
+/// The median of `data`, or `None` if it is empty
+///
+/// Uses quickselect rather than a full sort, averaging the two middle elements
+/// for even lengths. The input order is destroyed. Any NaN makes the median
+/// NaN, matching how NaN propagates through the mean.
+pub fn median(data: &mut [f32]) -> Option<f32> {
+    if data.iter().any(|x| x.is_nan()) {
+        return Some(f32::NAN);
+    }
+    percentile(data, 0.5)
+}
+
+/// Median absolute deviation from the median, or `None` if `data` is empty
+///
+/// The slice is overwritten with the absolute deviations `|x - median|`, then
+/// partitioned again to find their median. A NaN anywhere makes the result NaN,
+/// as with [`median`].
+pub fn mad(data: &mut [f32]) -> Option<f32> {
+    let center = median(data)?;
+    abs_deviation_inplace(data, center);
+    median(data)
+}
+
+#[cfg(target_arch = "x86_64")]
+fn abs_deviation_inplace(data: &mut [f32], center: f32) {
+    if has_feature!("avx") {
+        unsafe { abs_deviation_inplace_avx(data, center) }
+    } else {
+        abs_deviation_inplace_scalar(data, center)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn abs_deviation_inplace(data: &mut [f32], center: f32) {
+    abs_deviation_inplace_scalar(data, center)
+}
+
+fn abs_deviation_inplace_scalar(data: &mut [f32], center: f32) {
+    for x in data {
+        *x = (*x - center).abs();
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn abs_deviation_inplace_avx(data: &mut [f32], center: f32) {
+    let center_v = _mm256_set1_ps(center);
+    // Clearing the sign bit is the absolute value
+    let sign_mask = _mm256_set1_ps(-0.0);
+    let mut i = 0;
+
+    // Process 8 floats at a time using AVX
+    while i + 8 <= data.len() {
+        unsafe {
+            let ptr = data.as_mut_ptr().add(i);
+            let deviation = _mm256_sub_ps(_mm256_loadu_ps(ptr), center_v);
+            _mm256_storeu_ps(ptr, _mm256_andnot_ps(sign_mask, deviation));
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    abs_deviation_inplace_scalar(&mut data[i..], center);
+}
+
+/// Mean after discarding the lowest and highest `trim_fraction` of the values,
+/// or `None` if `data` is empty or `trim_fraction` is outside `0.0..0.5`
+///
+/// `floor(len * trim_fraction)` values are dropped from each end using two
+/// `select_nth_unstable_by` partitions, so the slice is left reordered. Values
+/// are ordered by `f32::total_cmp`, which places positive NaNs above `+inf`
+/// and negative NaNs below `-inf`.
+pub fn trimmed_mean(data: &mut [f32], trim_fraction: f64) -> Option<f32> {
+    if data.is_empty() || !(0.0..0.5).contains(&trim_fraction) {
+        return None;
+    }
+    let trim = (data.len() as f64 * trim_fraction) as usize;
+    if trim == 0 {
+        return Some(calculate_mean_simd(data));
+    }
+
+    // Move the `trim` smallest values below the partition point, then the
+    // `trim` largest of the rest above the second one
+    let (_, _, rest) = data.select_nth_unstable_by(trim - 1, f32::total_cmp);
+    let keep = rest.len() - trim;
+    rest.select_nth_unstable_by(keep, f32::total_cmp);
+    Some(calculate_mean_simd(&rest[..keep]))
+}
+
+/// The `k` largest values in descending order, with `k` clamped to `data.len()`
+///
+/// Partitions a copy of `data` and sorts only the top `k`, which is much cheaper
+/// than a full sort for small `k`. Values are ordered by `f32::total_cmp`, so
+/// positive NaNs rank above `+inf` and negative NaNs below `-inf`.
+pub fn top_k(data: &[f32], k: usize) -> Vec<f32> {
+    let k = k.min(data.len());
+    if k == 0 {
+        return Vec::new();
+    }
+    let descending = |a: &f32, b: &f32| b.total_cmp(a);
+
+    let mut values = data.to_vec();
+    values.select_nth_unstable_by(k - 1, descending);
+    values.truncate(k);
+    values.sort_unstable_by(descending);
+    values
+}
+
+/// Summary statistics gathered in a single pass over the data
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub mean: f32,
+    /// Population variance
+    pub variance: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+/// Calculate count, mean, variance, min and max in a single SIMD pass, or
+/// `None` for an empty slice
+///
+/// Sums are accumulated in f64 relative to the first finite element (the
+/// shifted-data trick), which keeps the one-pass variance from cancelling when
+/// the mean is large compared to the spread. Infinities still make the mean
+/// infinite and the variance NaN. `min` and `max` ignore NaNs like `simd_min`
+/// and `simd_max`.
+pub fn simd_summary(data: &[f32]) -> Option<Summary> {
+    let (mean, m2, min, max) = summary_moments(data)?;
+    Some(Summary {
+        count: data.len(),
+        mean: mean as f32,
+        variance: (m2 / data.len() as f64) as f32,
+        min,
+        max,
+    })
+}
+
+/// Mean, sum of squared deviations, min and max in f64, or `None` for an empty slice
+fn summary_moments(data: &[f32]) -> Option<(f64, f64, f32, f32)> {
+    if data.is_empty() {
+        return None;
+    }
+    let shift = data.iter().copied().find(|x| x.is_finite()).unwrap_or(0.0);
+    let (sum, sumsq, min, max) = summary_accumulate(data, shift);
+
+    let n = data.len() as f64;
+    let m2 = sumsq - sum * sum / n;
+    // Rounding can leave a tiny negative value when all elements are equal
+    let m2 = if m2 < 0.0 { 0.0 } else { m2 };
+    Some((shift as f64 + sum / n, m2, min, max))
+}
+
+#[cfg(target_arch = "x86_64")]
+fn summary_accumulate(data: &[f32], shift: f32) -> (f64, f64, f32, f32) {
+    if has_feature!("avx") {
+        unsafe { summary_accumulate_avx(data, shift) }
+    } else {
+        summary_accumulate_scalar(data, shift)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn summary_accumulate(data: &[f32], shift: f32) -> (f64, f64, f32, f32) {
+    summary_accumulate_scalar(data, shift)
+}
+
+/// Shifted sum and sum of squares in f64, min and max
+fn summary_accumulate_scalar(data: &[f32], shift: f32) -> (f64, f64, f32, f32) {
+    data.iter().fold((0.0, 0.0, f32::NAN, f32::NAN), |(sum, sumsq, min, max), &x| {
+        let d = x as f64 - shift as f64;
+        (sum + d, sumsq + d * d, min.min(x), max.max(x))
+    })
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn summary_accumulate_avx(data: &[f32], shift: f32) -> (f64, f64, f32, f32) {
+    let shift_vec = _mm256_set1_pd(shift as f64);
+    let mut sum_low = _mm256_setzero_pd();
+    let mut sum_high = _mm256_setzero_pd();
+    let mut sumsq_low = _mm256_setzero_pd();
+    let mut sumsq_high = _mm256_setzero_pd();
+    let mut min = _mm256_set1_ps(f32::INFINITY);
+    let mut max = _mm256_set1_ps(f32::NEG_INFINITY);
+    let mut i = 0;
+
+    // Process 8 floats at a time, updating every accumulator per load: the
+    // halves are widened to f64 before shifting so the sums don't round in f32.
+    // Accumulators stay the second min/max operand so NaN lanes are skipped.
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
+            let low = _mm256_sub_pd(_mm256_cvtps_pd(_mm256_castps256_ps128(chunk)), shift_vec);
+            let high = _mm256_sub_pd(_mm256_cvtps_pd(_mm256_extractf128_ps(chunk, 1)), shift_vec);
+            sum_low = _mm256_add_pd(sum_low, low);
+            sum_high = _mm256_add_pd(sum_high, high);
+            sumsq_low = _mm256_add_pd(sumsq_low, _mm256_mul_pd(low, low));
+            sumsq_high = _mm256_add_pd(sumsq_high, _mm256_mul_pd(high, high));
+            min = _mm256_min_ps(chunk, min);
+            max = _mm256_max_ps(chunk, max);
+        }
+        i += 8;
+    }
+
+    let (min_lanes, max_lanes) = unsafe { (lanes256_ps(min), lanes256_ps(max)) };
+
+    // Handle remaining elements and fold in the reduced lanes
+    let (remaining_sum, remaining_sumsq, remaining_min, remaining_max) =
+        summary_accumulate_scalar(&data[i..], shift);
+    let mut min = min_lanes.iter().copied().fold(remaining_min, f32::min);
+    let mut max = max_lanes.iter().copied().fold(remaining_max, f32::max);
+
+    // The min/max accumulators start at +/-inf, so tell an all-NaN slice apart
+    if min == f32::INFINITY && max == f32::NEG_INFINITY && data.iter().all(|x| x.is_nan()) {
+        min = f32::NAN;
+        max = f32::NAN;
+    }
+
+    unsafe {
+        (
+            hsum256_pd(_mm256_add_pd(sum_low, sum_high)) + remaining_sum,
+            hsum256_pd(_mm256_add_pd(sumsq_low, sumsq_high)) + remaining_sumsq,
+            min,
+            max,
+        )
+    }
+}
+
+/// Mergeable partial sum for aggregating a mean across shards
+///
+/// Each shard is summed with SIMD into f64 accumulators and the partials are
+/// combined in f64, so neither long shards nor merging many of them adds f32
+/// rounding error. `merge` is associative
+/// (up to f64 rounding), so partials can be reduced in any grouping.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Partial {
+    pub sum: f64,
+    pub count: usize,
+}
+
+impl Partial {
+    /// Partial over one shard of the data
+    pub fn from_slice(data: &[f32]) -> Self {
+        Partial { sum: simd_sum_f64acc(data), count: data.len() }
+    }
+
+    /// Combine with the partial of another shard
+    pub fn merge(self, other: Partial) -> Partial {
+        Partial {
+            sum: self.sum + other.sum,
+            count: self.count + other.count,
+        }
+    }
+
+    /// The mean over every merged shard, or `None` if they were all empty
+    pub fn finish(self) -> Option<f32> {
+        (self.count > 0).then(|| (self.sum / self.count as f64) as f32)
+    }
+}
+
+/// Mean of a file of raw little-endian f32 values, or `None` for an empty file
+///
+/// The file is read in fixed-size chunks, each summed with SIMD into a
+/// [`Partial`], so memory use stays constant however large the file is. A
+/// length that isn't a multiple of 4 bytes is an `InvalidData` error.
+#[cfg(feature = "std")]
+pub fn mean_of_file(path: &Path) -> io::Result<Option<f32>> {
+    // A multiple of 4, so only the last, short chunk can end mid-value
+    const CHUNK_BYTES: usize = 1 << 16;
+
+    let mut file = File::open(path)?;
+    let mut bytes = vec![0u8; CHUNK_BYTES];
+    let mut values = Vec::with_capacity(CHUNK_BYTES / 4);
+    let mut total = Partial::default();
+
+    loop {
+        let filled = read_full(&mut file, &mut bytes)?;
+        let chunks = bytes[..filled].chunks_exact(4);
+        if !chunks.remainder().is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "file length is not a multiple of 4 bytes",
+            ));
+        }
+
+        values.clear();
+        values.extend(chunks.map(|value| f32::from_le_bytes([value[0], value[1], value[2], value[3]])));
+        total = total.merge(Partial::from_slice(&values));
+
+        if filled < CHUNK_BYTES {
+            return Ok(total.finish());
+        }
+    }
+}
+
+/// Mean of a file of raw little-endian f32 values, memory-mapped, or `None` for an empty file
+///
+/// The mapping is reinterpreted in place as `&[f32]` and passed straight to
+/// [`calculate_mean_simd`], so nothing is copied. A length that isn't a
+/// multiple of 4 bytes is an `InvalidData` error, as is a mapping that isn't
+/// 4-byte aligned (mappings start on a page boundary, so it never should be).
+/// Big-endian targets can't reinterpret the bytes and fall back to
+/// [`mean_of_file`].
+///
+/// The file must not be truncated or written to while it is mapped, by this
+/// process or any other: reads past a truncated end fault (`SIGBUS` on
+/// Unix), and concurrent writes make the result meaningless.
+#[cfg(feature = "mmap")]
+pub fn mean_of_mmap(path: &Path) -> io::Result<Option<f32>> {
+    if cfg!(target_endian = "big") {
+        return mean_of_file(path);
+    }
+
+    let file = File::open(path)?;
+    let len = file.metadata()?.len();
+    if len % 4 != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "file length is not a multiple of 4 bytes",
+        ));
+    }
+    if len == 0 {
+        return Ok(None);
+    }
+
+    // Sound as long as nothing truncates or writes the file while it is mapped
+    let map = unsafe { memmap2::Mmap::map(&file)? };
+    // Every bit pattern is a valid f32, so only alignment can split off bytes
+    let (prefix, data, suffix) = unsafe { map.align_to::<f32>() };
+    if !prefix.is_empty() || !suffix.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "mapping is not 4-byte aligned"));
+    }
+    Ok(mean_simd(data))
+}
+
+/// Read until `buf` is full or the reader runs out, returning how many bytes were read
+#[cfg(feature = "std")]
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(filled)
+}
+
+/// Streaming mean and variance using Welford's online algorithm
+///
+/// State is kept in f64 so long streams don't drift. Batches passed to
+/// [`RunningStats::extend`] are summarised with the same f64 SIMD pass as
+/// [`simd_summary`], without rounding to f32, and merged in with Chan et al.'s
+/// parallel update, so they agree with pushing the values one at a time.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RunningStats {
+    count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl RunningStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a single value
+    pub fn push(&mut self, x: f32) {
+        let x = x as f64;
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    /// Add a batch of values in one vectorized pass
+    pub fn extend(&mut self, data: &[f32]) {
+        let Some((batch_mean, batch_m2, _, _)) = summary_moments(data) else {
+            return;
+        };
+
+        let batch_count = data.len();
+        let count = self.count + batch_count;
+        let delta = batch_mean - self.mean;
+
+        self.mean += delta * batch_count as f64 / count as f64;
+        self.m2 += batch_m2 + delta * delta * self.count as f64 * batch_count as f64 / count as f64;
+        self.count = count;
+    }
+
+    /// Number of values seen so far
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Mean of the values seen so far, or `None` if there are none
+    pub fn mean(&self) -> Option<f32> {
+        (self.count > 0).then_some(self.mean as f32)
+    }
+
+    /// Population variance of the values seen so far, or `None` if there are none
+    pub fn variance(&self) -> Option<f32> {
+        (self.count > 0).then(|| (self.m2 / self.count as f64) as f32)
+    }
+}
+
+/// Calculate the dot product of two slices using SIMD operations (FMA or AVX on x86_64)
+#[cfg(target_arch = "x86_64")]
+pub fn simd_dot(a: &[f32], b: &[f32]) -> Result<f32, LengthMismatch> {
+    check_lengths(a, b)?;
+    if has_feature!("avx", "fma") {
+        Ok(unsafe { simd_dot_avx_fma(a, b) })
+    } else if has_feature!("avx") {
+        Ok(unsafe { simd_dot_avx(a, b) })
+    } else {
+        Ok(scalar_dot(a, b))
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_dot(a: &[f32], b: &[f32]) -> Result<f32, LengthMismatch> {
+    check_lengths(a, b)?;
+    Ok(scalar_dot(a, b))
+}
+
+fn check_lengths<T, U>(a: &[T], b: &[U]) -> Result<(), LengthMismatch> {
+    if a.len() == b.len() {
+        Ok(())
+    } else {
+        Err(LengthMismatch { left: a.len(), right: b.len() })
+    }
+}
+
+fn scalar_dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(&x, &y)| x * y).sum()
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx,fma")]
+unsafe fn simd_dot_avx_fma(a: &[f32], b: &[f32]) -> f32 {
+    let mut sum = _mm256_setzero_ps();
+    let mut i = 0;
+
+    // Process 8 pairs at a time using fused multiply-add
+    while i + 8 <= a.len() {
+        unsafe {
+            let x = _mm256_loadu_ps(a.as_ptr().add(i));
+            let y = _mm256_loadu_ps(b.as_ptr().add(i));
+            sum = _mm256_fmadd_ps(x, y, sum);
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    unsafe { hsum256_ps(sum) + scalar_dot(&a[i..], &b[i..]) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn simd_dot_avx(a: &[f32], b: &[f32]) -> f32 {
+    let mut sum = _mm256_setzero_ps();
+    let mut i = 0;
+
+    // Process 8 pairs at a time using AVX
+    while i + 8 <= a.len() {
+        unsafe {
+            let x = _mm256_loadu_ps(a.as_ptr().add(i));
+            let y = _mm256_loadu_ps(b.as_ptr().add(i));
+            sum = _mm256_add_ps(sum, _mm256_mul_ps(x, y));
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    unsafe { hsum256_ps(sum) + scalar_dot(&a[i..], &b[i..]) }
+}
+
+/// Calculate the population covariance of paired samples, failing if the lengths differ
+///
+/// Both inputs are shifted by their first element before the single pass, as
+/// in [`simd_summary`], so large offsets don't cancel away the result.
+pub fn simd_covariance(a: &[f32], b: &[f32]) -> Result<f32, LengthMismatch> {
+    check_lengths(a, b)?;
+    let m = co_moments(a, b);
+    let n = a.len() as f32;
+    Ok((m.sum_ab - m.sum_a * m.sum_b / n) / n)
+}
+
+/// Calculate the Pearson correlation coefficient of paired samples, failing if the lengths differ
+///
+/// `NaN` when either input is empty or constant.
+pub fn simd_correlation(a: &[f32], b: &[f32]) -> Result<f32, LengthMismatch> {
+    check_lengths(a, b)?;
+    let m = co_moments(a, b);
+    let n = a.len() as f32;
+    let cov = m.sum_ab - m.sum_a * m.sum_b / n;
+    let var_a = m.sum_aa - m.sum_a * m.sum_a / n;
+    let var_b = m.sum_bb - m.sum_b * m.sum_b / n;
+    Ok(cov / (var_a * var_b).sqrt())
+}
+
+/// Sums of the shifted samples, their products and their squares
+#[derive(Debug, Clone, Copy, Default)]
+struct CoMoments {
+    sum_a: f32,
+    sum_b: f32,
+    sum_ab: f32,
+    sum_aa: f32,
+    sum_bb: f32,
+}
+
+impl CoMoments {
+    fn add(self, other: CoMoments) -> CoMoments {
+        CoMoments {
+            sum_a: self.sum_a + other.sum_a,
+            sum_b: self.sum_b + other.sum_b,
+            sum_ab: self.sum_ab + other.sum_ab,
+            sum_aa: self.sum_aa + other.sum_aa,
+            sum_bb: self.sum_bb + other.sum_bb,
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn co_moments(a: &[f32], b: &[f32]) -> CoMoments {
+    let shift = (a.first().copied().unwrap_or(0.0), b.first().copied().unwrap_or(0.0));
+    if has_feature!("avx") {
+        unsafe { co_moments_avx(a, b, shift) }
+    } else {
+        co_moments_scalar(a, b, shift)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn co_moments(a: &[f32], b: &[f32]) -> CoMoments {
+    let shift = (a.first().copied().unwrap_or(0.0), b.first().copied().unwrap_or(0.0));
+    co_moments_scalar(a, b, shift)
+}
+
+fn co_moments_scalar(a: &[f32], b: &[f32], (shift_a, shift_b): (f32, f32)) -> CoMoments {
+    a.iter().zip(b).fold(CoMoments::default(), |m, (&x, &y)| {
+        let (x, y) = (x - shift_a, y - shift_b);
+        m.add(CoMoments { sum_a: x, sum_b: y, sum_ab: x * y, sum_aa: x * x, sum_bb: y * y })
+    })
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn co_moments_avx(a: &[f32], b: &[f32], (shift_a, shift_b): (f32, f32)) -> CoMoments {
+    let shift_a_vec = _mm256_set1_ps(shift_a);
+    let shift_b_vec = _mm256_set1_ps(shift_b);
+    let mut sum_a = _mm256_setzero_ps();
+    let mut sum_b = _mm256_setzero_ps();
+    let mut sum_ab = _mm256_setzero_ps();
+    let mut sum_aa = _mm256_setzero_ps();
+    let mut sum_bb = _mm256_setzero_ps();
+    let mut i = 0;
+
+    // Process 8 pairs at a time, updating all five accumulators per load
+    while i + 8 <= a.len() {
+        unsafe {
+            let x = _mm256_sub_ps(_mm256_loadu_ps(a.as_ptr().add(i)), shift_a_vec);
+            let y = _mm256_sub_ps(_mm256_loadu_ps(b.as_ptr().add(i)), shift_b_vec);
+            sum_a = _mm256_add_ps(sum_a, x);
+            sum_b = _mm256_add_ps(sum_b, y);
+            sum_ab = _mm256_add_ps(sum_ab, _mm256_mul_ps(x, y));
+            sum_aa = _mm256_add_ps(sum_aa, _mm256_mul_ps(x, x));
+            sum_bb = _mm256_add_ps(sum_bb, _mm256_mul_ps(y, y));
+        }
+        i += 8;
+    }
+
+    let simd = unsafe {
+        CoMoments {
+            sum_a: hsum256_ps(sum_a),
+            sum_b: hsum256_ps(sum_b),
+            sum_ab: hsum256_ps(sum_ab),
+            sum_aa: hsum256_ps(sum_aa),
+            sum_bb: hsum256_ps(sum_bb),
+        }
+    };
+
+    // Handle remaining elements
+    simd.add(co_moments_scalar(&a[i..], &b[i..], (shift_a, shift_b)))
+}
+
+/// Calculate `sum(v * w) / sum(w)`, failing if the lengths differ
+///
+/// `Ok(None)` when the weights sum to zero, including for empty input.
+#[cfg(target_arch = "x86_64")]
+pub fn weighted_mean(values: &[f32], weights: &[f32]) -> Result<Option<f32>, LengthMismatch> {
+    check_lengths(values, weights)?;
+    let (weighted_sum, weight_sum) = if has_feature!("avx", "fma") {
+        unsafe { weighted_sums_avx_fma(values, weights) }
+    } else {
+        weighted_sums_scalar(values, weights)
+    };
+    Ok((weight_sum != 0.0).then(|| weighted_sum / weight_sum))
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn weighted_mean(values: &[f32], weights: &[f32]) -> Result<Option<f32>, LengthMismatch> {
+    check_lengths(values, weights)?;
+    let (weighted_sum, weight_sum) = weighted_sums_scalar(values, weights);
+    Ok((weight_sum != 0.0).then(|| weighted_sum / weight_sum))
+}
+
+fn weighted_sums_scalar(values: &[f32], weights: &[f32]) -> (f32, f32) {
+    values.iter().zip(weights).fold((0.0, 0.0), |(weighted_sum, weight_sum), (&v, &w)| {
+        (weighted_sum + v * w, weight_sum + w)
+    })
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx,fma")]
+unsafe fn weighted_sums_avx_fma(values: &[f32], weights: &[f32]) -> (f32, f32) {
+    let mut weighted_sum = _mm256_setzero_ps();
+    let mut weight_sum = _mm256_setzero_ps();
+    let mut i = 0;
+
+    // Process 8 pairs at a time, accumulating v*w and w from the same loads
+    while i + 8 <= values.len() {
+        unsafe {
+            let v = _mm256_loadu_ps(values.as_ptr().add(i));
+            let w = _mm256_loadu_ps(weights.as_ptr().add(i));
+            weighted_sum = _mm256_fmadd_ps(v, w, weighted_sum);
+            weight_sum = _mm256_add_ps(weight_sum, w);
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    let (remaining_weighted, remaining_weight) = weighted_sums_scalar(&values[i..], &weights[i..]);
+
+    unsafe {
+        (
+            hsum256_ps(weighted_sum) + remaining_weighted,
+            hsum256_ps(weight_sum) + remaining_weight,
+        )
+    }
+}
+
+/// Normalized mean squared error `sum((r - e)^2) / sum(r^2)`, failing if the lengths differ
+///
+/// Both sums come from a single pass with two FMA accumulators. When the
+/// reference has zero energy the ratio is undefined: `0.0` if the estimate
+/// matches it exactly (including for empty input), `NaN` otherwise.
+#[cfg(target_arch = "x86_64")]
+pub fn nmse(reference: &[f32], estimate: &[f32]) -> Result<f32, LengthMismatch> {
+    check_lengths(reference, estimate)?;
+    let sums = if has_feature!("avx", "fma") {
+        unsafe { nmse_sums_avx_fma(reference, estimate) }
+    } else {
+        nmse_sums_scalar(reference, estimate)
+    };
+    Ok(nmse_ratio(sums))
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn nmse(reference: &[f32], estimate: &[f32]) -> Result<f32, LengthMismatch> {
+    check_lengths(reference, estimate)?;
+    Ok(nmse_ratio(nmse_sums_scalar(reference, estimate)))
+}
+
+fn nmse_ratio((error_energy, reference_energy): (f32, f32)) -> f32 {
+    if reference_energy != 0.0 {
+        error_energy / reference_energy
+    } else if error_energy == 0.0 {
+        0.0
+    } else {
+        f32::NAN
+    }
+}
+
+fn nmse_sums_scalar(reference: &[f32], estimate: &[f32]) -> (f32, f32) {
+    reference.iter().zip(estimate).fold((0.0, 0.0), |(error_energy, reference_energy), (&r, &e)| {
+        (error_energy + (r - e) * (r - e), reference_energy + r * r)
+    })
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx,fma")]
+unsafe fn nmse_sums_avx_fma(reference: &[f32], estimate: &[f32]) -> (f32, f32) {
+    let mut error_energy = _mm256_setzero_ps();
+    let mut reference_energy = _mm256_setzero_ps();
+    let mut i = 0;
+
+    // Process 8 pairs at a time, accumulating (r-e)^2 and r^2 from the same loads
+    while i + 8 <= reference.len() {
+        unsafe {
+            let r = _mm256_loadu_ps(reference.as_ptr().add(i));
+            let e = _mm256_loadu_ps(estimate.as_ptr().add(i));
+            let diff = _mm256_sub_ps(r, e);
+            error_energy = _mm256_fmadd_ps(diff, diff, error_energy);
+            reference_energy = _mm256_fmadd_ps(r, r, reference_energy);
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    let (remaining_error, remaining_reference) = nmse_sums_scalar(&reference[i..], &estimate[i..]);
+
+    unsafe {
+        (
+            hsum256_ps(error_energy) + remaining_error,
+            hsum256_ps(reference_energy) + remaining_reference,
+        )
+    }
+}
+
+/// Element-wise sum `out[i] = a[i] + b[i]`, failing if the three lengths differ
+#[cfg(target_arch = "x86_64")]
+pub fn simd_add(a: &[f32], b: &[f32], out: &mut [f32]) -> Result<(), LengthMismatch> {
+    check_lengths(a, b)?;
+    check_lengths(a, out)?;
+    if has_feature!("avx") {
+        unsafe { simd_add_avx(a, b, out) }
+    } else {
+        scalar_add(a, b, out)
+    }
+    Ok(())
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_add(a: &[f32], b: &[f32], out: &mut [f32]) -> Result<(), LengthMismatch> {
+    check_lengths(a, b)?;
+    check_lengths(a, out)?;
+    scalar_add(a, b, out);
+    Ok(())
+}
+
+/// Element-wise sum of `a` and `b` into a new vector
+pub fn simd_add_vec(a: &[f32], b: &[f32]) -> Result<Vec<f32>, LengthMismatch> {
+    let mut out = vec![0.0; a.len()];
+    simd_add(a, b, &mut out)?;
+    Ok(out)
+}
+
+fn scalar_add(a: &[f32], b: &[f32], out: &mut [f32]) {
+    for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+        *o = x + y;
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn simd_add_avx(a: &[f32], b: &[f32], out: &mut [f32]) {
+    let mut i = 0;
+
+    // Process 8 pairs at a time using AVX
+    while i + 8 <= a.len() {
+        unsafe {
+            let x = _mm256_loadu_ps(a.as_ptr().add(i));
+            let y = _mm256_loadu_ps(b.as_ptr().add(i));
+            _mm256_storeu_ps(out.as_mut_ptr().add(i), _mm256_add_ps(x, y));
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    scalar_add(&a[i..], &b[i..], &mut out[i..]);
+}
+
+/// Widen every element of `src` into `dst`, failing if the lengths differ
+#[cfg(target_arch = "x86_64")]
+pub fn simd_f32_to_f64(src: &[f32], dst: &mut [f64]) -> Result<(), LengthMismatch> {
+    check_lengths(src, dst)?;
+    if has_feature!("avx") {
+        unsafe { f32_to_f64_avx(src, dst) }
+    } else {
+        f32_to_f64_scalar(src, dst)
+    }
+    Ok(())
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_f32_to_f64(src: &[f32], dst: &mut [f64]) -> Result<(), LengthMismatch> {
+    check_lengths(src, dst)?;
+    f32_to_f64_scalar(src, dst);
+    Ok(())
+}
+
+/// Widen every element of `src` into a new vector
+pub fn simd_f32_to_f64_vec(src: &[f32]) -> Vec<f64> {
+    let mut dst = vec![0.0; src.len()];
+    simd_f32_to_f64(src, &mut dst).expect("dst has the length of src");
+    dst
+}
+
+/// Narrow every element of `src` into `dst`, rounding to nearest, failing if the lengths differ
+#[cfg(target_arch = "x86_64")]
+pub fn simd_f64_to_f32(src: &[f64], dst: &mut [f32]) -> Result<(), LengthMismatch> {
+    check_lengths(src, dst)?;
+    if has_feature!("avx") {
+        unsafe { f64_to_f32_avx(src, dst) }
+    } else {
+        f64_to_f32_scalar(src, dst)
+    }
+    Ok(())
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn simd_f64_to_f32(src: &[f64], dst: &mut [f32]) -> Result<(), LengthMismatch> {
+    check_lengths(src, dst)?;
+    f64_to_f32_scalar(src, dst);
+    Ok(())
+}
+
+/// Narrow every element of `src` into a new vector
+pub fn simd_f64_to_f32_vec(src: &[f64]) -> Vec<f32> {
+    let mut dst = vec![0.0; src.len()];
+    simd_f64_to_f32(src, &mut dst).expect("dst has the length of src");
+    dst
+}
+
+fn f32_to_f64_scalar(src: &[f32], dst: &mut [f64]) {
+    for (d, &x) in dst.iter_mut().zip(src) {
+        *d = x as f64;
+    }
+}
+
+fn f64_to_f32_scalar(src: &[f64], dst: &mut [f32]) {
+    for (d, &x) in dst.iter_mut().zip(src) {
+        *d = x as f32;
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn f32_to_f64_avx(src: &[f32], dst: &mut [f64]) {
+    let mut i = 0;
+
+    // Process 8 floats at a time, widening each 128-bit half to 4 doubles
+    while i + 8 <= src.len() {
+        unsafe {
+            let chunk = _mm256_loadu_ps(src.as_ptr().add(i));
+            let low = _mm256_cvtps_pd(_mm256_castps256_ps128(chunk));
+            let high = _mm256_cvtps_pd(_mm256_extractf128_ps(chunk, 1));
+            _mm256_storeu_pd(dst.as_mut_ptr().add(i), low);
+            _mm256_storeu_pd(dst.as_mut_ptr().add(i + 4), high);
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    f32_to_f64_scalar(&src[i..], &mut dst[i..]);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn f64_to_f32_avx(src: &[f64], dst: &mut [f32]) {
+    let mut i = 0;
+
+    // Process 8 doubles at a time, narrowing each group of 4 to a 128-bit half
+    while i + 8 <= src.len() {
+        unsafe {
+            let low = _mm256_cvtpd_ps(_mm256_loadu_pd(src.as_ptr().add(i)));
+            let high = _mm256_cvtpd_ps(_mm256_loadu_pd(src.as_ptr().add(i + 4)));
+            _mm256_storeu_ps(dst.as_mut_ptr().add(i), _mm256_set_m128(high, low));
+        }
+        i += 8;
+    }
+
+    // Handle remaining elements
+    f64_to_f32_scalar(&src[i..], &mut dst[i..]);
+}
+
+/// Running totals where `out[i]` is the sum of `data[..=i]`
+#[cfg(target_arch = "x86_64")]
+pub fn prefix_sum(data: &[f32]) -> Vec<f32> {
+    let mut out = vec![0.0; data.len()];
+    if has_feature!("avx") {
+        unsafe { prefix_sum_avx(data, &mut out) }
+    } else {
+        prefix_sum_scalar(data, &mut out, 0.0)
+    }
+    out
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn prefix_sum(data: &[f32]) -> Vec<f32> {
+    let mut out = vec![0.0; data.len()];
+    prefix_sum_scalar(data, &mut out, 0.0);
+    out
+}
+
+fn prefix_sum_scalar(data: &[f32], out: &mut [f32], mut carry: f32) {
+    for (o, &x) in out.iter_mut().zip(data) {
+        carry += x;
+        *o = carry;
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn prefix_sum_avx(data: &[f32], out: &mut [f32]) {
+    let zero = _mm256_setzero_ps();
+    let mut carry = 0.0;
+    let mut i = 0;
+
+    // Scan 8 floats at a time: shift-and-add within each 128-bit half, add the
+    // low half's total into the high half, then add the running total so far
+    while i + 8 <= data.len() {
+        unsafe {
+            let mut x = _mm256_loadu_ps(data.as_ptr().add(i));
+            let shifted = _mm256_permute_ps(x, 0b10_01_00_11);
+            x = _mm256_add_ps(x, _mm256_blend_ps(shifted, zero, 0b0001_0001));
+            let shifted = _mm256_permute_ps(x, 0b01_00_11_10);
+            x = _mm256_add_ps(x, _mm256_blend_ps(shifted, zero, 0b0011_0011));
+            let low_total = _mm256_permute_ps(x, 0b11_11_11_11);
+            x = _mm256_add_ps(x, _mm256_permute2f128_ps(low_total, low_total, 0x08));
+            x = _mm256_add_ps(x, _mm256_set1_ps(carry));
+            _mm256_storeu_ps(out.as_mut_ptr().add(i), x);
+        }
+        carry = out[i + 7];
+        i += 8;
+    }
+
+    // Handle remaining elements
+    prefix_sum_scalar(&data[i..], &mut out[i..], carry);
+}
+
+/// Mean of every `window`-long run, giving `data.len() - window + 1` values
+///
+/// Each window's sum is the difference of two prefix sums, so every mean is
+/// O(1) after one SIMD scan. The prefix sums are accumulated in f64: a
+/// difference of two f32 prefix sums cancels against the large running total
+/// on long inputs. A window longer than the data yields an empty vector.
+pub fn moving_average(data: &[f32], window: usize) -> Result<Vec<f32>, ZeroWindow> {
+    if window == 0 {
+        return Err(ZeroWindow);
+    }
+    if window > data.len() {
+        return Ok(Vec::new());
+    }
+    let prefix = prefix_sum_f64acc(data);
+    let mut out = Vec::with_capacity(data.len() - window + 1);
+    out.push((prefix[window - 1] / window as f64) as f32);
+    for (&before, &end) in prefix.iter().zip(&prefix[window..]) {
+        out.push(((end - before) / window as f64) as f32);
+    }
+    Ok(out)
+}
+
+/// Running totals of `data` accumulated in f64, like [`prefix_sum`] but widened
+#[cfg(target_arch = "x86_64")]
+fn prefix_sum_f64acc(data: &[f32]) -> Vec<f64> {
+    let mut out = vec![0.0; data.len()];
+    if has_feature!("avx") {
+        unsafe { prefix_sum_f64acc_avx(data, &mut out) }
+    } else {
+        prefix_sum_f64acc_scalar(data, &mut out, 0.0)
+    }
+    out
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn prefix_sum_f64acc(data: &[f32]) -> Vec<f64> {
+    let mut out = vec![0.0; data.len()];
+    prefix_sum_f64acc_scalar(data, &mut out, 0.0);
+    out
+}
+
+fn prefix_sum_f64acc_scalar(data: &[f32], out: &mut [f64], mut carry: f64) {
+    for (o, &x) in out.iter_mut().zip(data) {
+        carry += x as f64;
+        *o = carry;
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn prefix_sum_f64acc_avx(data: &[f32], out: &mut [f64]) {
+    let zero = _mm256_setzero_pd();
+    let mut carry = 0.0;
+    let mut i = 0;
+
+    // Widen 4 floats at a time and scan them as in `prefix_sum_avx`: add each
+    // 128-bit half's first double into its second, then the low half's total
+    // into the high half, then the running total so far
+    while i + 4 <= data.len() {
+        unsafe {
+            let mut x = _mm256_cvtps_pd(_mm_loadu_ps(data.as_ptr().add(i)));
+            let shifted = _mm256_permute_pd(x, 0b0000);
+            x = _mm256_add_pd(x, _mm256_blend_pd(shifted, zero, 0b0101));
+            let low_total = _mm256_permute_pd(x, 0b1111);
+            x = _mm256_add_pd(x, _mm256_permute2f128_pd(low_total, low_total, 0x08));
+            x = _mm256_add_pd(x, _mm256_set1_pd(carry));
+            _mm256_storeu_pd(out.as_mut_ptr().add(i), x);
+        }
+        carry = out[i + 3];
+        i += 4;
+    }
+
+    // Handle remaining elements
+    prefix_sum_f64acc_scalar(&data[i..], &mut out[i..], carry);
+}
+
+/// Exponential moving average `out[i] = alpha * data[i] + (1 - alpha) * out[i - 1]`
+///
+/// Seeded with `out[0] = data[0]`. The recurrence is inherently sequential, so
+/// this is a scalar loop.
+pub fn ema(data: &[f32], alpha: f32) -> Result<Vec<f32>, InvalidAlpha> {
+    if !(alpha > 0.0 && alpha <= 1.0) {
+        return Err(InvalidAlpha(alpha));
+    }
+    let mut out = Vec::with_capacity(data.len());
+    let mut prev = match data.first() {
+        Some(&first) => first,
+        None => return Ok(out),
+    };
+    for &x in data {
+        prev = alpha * x + (1.0 - alpha) * prev;
+        out.push(prev);
+    }
+    Ok(out)
+}
+
+/// Calculate mean of f64 data using SIMD operations (AVX on x86_64)
+#[cfg(target_arch = "x86_64")]
+pub fn calculate_mean_simd_f64(data: &[f64]) -> f64 {
+    if has_feature!("avx") {
+        unsafe { calculate_mean_simd_avx_f64(data) }
+    } else {
+        calculate_mean_chunks(data)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn calculate_mean_simd_f64(data: &[f64]) -> f64 {
+    calculate_mean_chunks(data)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn calculate_mean_simd_avx_f64(data: &[f64]) -> f64 {
+    let mut sum = _mm256_setzero_pd();
+    let mut i = 0;
+
+    // Process 4 doubles at a time using AVX
+    while i + 4 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_pd(data.as_ptr().add(i));
+            sum = _mm256_add_pd(sum, chunk);
+        }
+        i += 4;
+    }
+
+    let simd_sum = unsafe { hsum256_pd(sum) };
+
+    // Handle remaining elements
+    let remaining_sum: f64 = data[i..].iter().sum();
+
+    (simd_sum + remaining_sum) / data.len() as f64
+}
+
+/// Calculate mean of i32 data using SIMD operations (AVX2 or SSE2 on x86_64)
+///
+/// Lanes are widened to i64 before accumulating, so the sum cannot overflow.
+/// 256-bit integer instructions need AVX2 rather than AVX, so that is what
+/// the wide path checks for.
+#[cfg(target_arch = "x86_64")]
+pub fn calculate_mean_i32(data: &[i32]) -> f64 {
+    let sum = if has_feature!("avx2") {
+        unsafe { sum_i32_avx2(data) }
+    } else if has_feature!("sse2") {
+        unsafe { sum_i32_sse2(data) }
+    } else {
+        sum_i32_scalar(data)
+    };
+    sum as f64 / data.len() as f64
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn calculate_mean_i32(data: &[i32]) -> f64 {
+    sum_i32_scalar(data) as f64 / data.len() as f64
+}
+
+fn sum_i32_scalar(data: &[i32]) -> i64 {
+    data.iter().map(|&x| x as i64).sum()
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn sum_i32_avx2(data: &[i32]) -> i64 {
+    let mut sum_lo = _mm256_setzero_si256();
+    let mut sum_hi = _mm256_setzero_si256();
+    let mut i = 0;
+
+    // Process 8 ints at a time, sign-extending each half to four i64 lanes
+    while i + 8 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_si256(data.as_ptr().add(i).cast());
+            let lo = _mm256_cvtepi32_epi64(_mm256_castsi256_si128(chunk));
+            let hi = _mm256_cvtepi32_epi64(_mm256_extracti128_si256(chunk, 1));
+            sum_lo = _mm256_add_epi64(sum_lo, lo);
+            sum_hi = _mm256_add_epi64(sum_hi, hi);
+        }
+        i += 8;
+    }
+
+    let mut lanes = [0i64; 4];
+    unsafe {
+        _mm256_storeu_si256(lanes.as_mut_ptr().cast(), _mm256_add_epi64(sum_lo, sum_hi));
+    }
+
+    // Handle remaining elements
+    lanes.iter().sum::<i64>() + sum_i32_scalar(&data[i..])
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn sum_i32_sse2(data: &[i32]) -> i64 {
+    let mut sum = _mm_setzero_si128();
+    let mut i = 0;
+
+    // Process 4 ints at a time. SSE2 has no sign-extending widen, so
+    // interleave each int with its sign mask to form two i64 lanes per half.
+    while i + 4 <= data.len() {
+        unsafe {
+            let chunk = _mm_loadu_si128(data.as_ptr().add(i).cast());
+            let sign = _mm_srai_epi32(chunk, 31);
+            sum = _mm_add_epi64(sum, _mm_unpacklo_epi32(chunk, sign));
+            sum = _mm_add_epi64(sum, _mm_unpackhi_epi32(chunk, sign));
+        }
+        i += 4;
+    }
+
+    let mut lanes = [0i64; 2];
+    unsafe {
+        _mm_storeu_si128(lanes.as_mut_ptr().cast(), sum);
+    }
+
+    // Handle remaining elements
+    lanes.iter().sum::<i64>() + sum_i32_scalar(&data[i..])
+}
+
+/// Calculate mean of u8 data using SIMD operations (AVX2 or SSE2 on x86_64)
+#[cfg(target_arch = "x86_64")]
+pub fn calculate_mean_u8(data: &[u8]) -> f64 {
+    let sum = if has_feature!("avx2") {
+        unsafe { sum_u8_avx2(data) }
+    } else if has_feature!("sse2") {
+        unsafe { sum_u8_sse2(data) }
+    } else {
+        sum_u8_scalar(data)
+    };
+    sum as f64 / data.len() as f64
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn calculate_mean_u8(data: &[u8]) -> f64 {
+    sum_u8_scalar(data) as f64 / data.len() as f64
+}
+
+fn sum_u8_scalar(data: &[u8]) -> u64 {
+    data.iter().map(|&x| x as u64).sum()
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn sum_u8_avx2(data: &[u8]) -> u64 {
+    let zero = _mm256_setzero_si256();
+    let mut sum = _mm256_setzero_si256();
+    let mut i = 0;
+
+    // Process 32 bytes at a time. The sum of absolute differences against zero
+    // adds each group of 8 bytes into one of the four u64 lanes.
+    while i + 32 <= data.len() {
+        unsafe {
+            let chunk = _mm256_loadu_si256(data.as_ptr().add(i).cast());
+            sum = _mm256_add_epi64(sum, _mm256_sad_epu8(chunk, zero));
+        }
+        i += 32;
+    }
+
+    let mut lanes = [0u64; 4];
+    unsafe {
+        _mm256_storeu_si256(lanes.as_mut_ptr().cast(), sum);
+    }
+
+    // Handle remaining elements
+    lanes.iter().sum::<u64>() + sum_u8_scalar(&data[i..])
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn sum_u8_sse2(data: &[u8]) -> u64 {
+    let zero = _mm_setzero_si128();
+    let mut sum = _mm_setzero_si128();
+    let mut i = 0;
+
+    // Process 16 bytes at a time, summing each group of 8 into a u64 lane
+    while i + 16 <= data.len() {
+        unsafe {
+            let chunk = _mm_loadu_si128(data.as_ptr().add(i).cast());
+            sum = _mm_add_epi64(sum, _mm_sad_epu8(chunk, zero));
+        }
+        i += 16;
+    }
+
+    let mut lanes = [0u64; 2];
+    unsafe {
+        _mm_storeu_si128(lanes.as_mut_ptr().cast(), sum);
+    }
+
+    // Handle remaining elements
+    lanes.iter().sum::<u64>() + sum_u8_scalar(&data[i..])
+}
+
+/// Guard that flushes denormal floats to zero on the current thread until dropped
+///
+/// Sets the MXCSR flush-to-zero (FTZ) and denormals-are-zero (DAZ) bits on
+/// creation and restores the previous MXCSR on drop. Denormal inputs are then
+/// read as zero and denormal results written as zero, giving up precision
+/// below `f32::MIN_POSITIVE` (about 1.2e-38) to avoid the microcode assist that
+/// makes denormal arithmetic many times slower on most x86 CPUs.
+///
+/// MXCSR is per-thread state, so the guard is `!Send`; threads spawned while
+/// it is alive (such as rayon workers) are unaffected.
+#[cfg(target_arch = "x86_64")]
+pub struct DenormalsFlushed {
+    saved: u32,
+    _not_send: PhantomData<*const ()>,
+}
+
+#[cfg(target_arch = "x86_64")]
+impl DenormalsFlushed {
+    const FLUSH_ZERO: u32 = 1 << 15;
+    const DENORMALS_ZERO: u32 = 1 << 6;
+
+    pub fn new() -> Self {
+        let saved = read_mxcsr();
+        write_mxcsr(saved | Self::FLUSH_ZERO | Self::DENORMALS_ZERO);
+        DenormalsFlushed { saved, _not_send: PhantomData }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl Default for DenormalsFlushed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl Drop for DenormalsFlushed {
+    fn drop(&mut self) {
+        write_mxcsr(self.saved);
+    }
+}
+
+// `_mm_getcsr`/`_mm_setcsr` are deprecated in favour of inline assembly
+#[cfg(target_arch = "x86_64")]
+fn read_mxcsr() -> u32 {
+    let mut csr = 0u32;
+    unsafe {
+        core::arch::asm!("stmxcsr [{}]", in(reg) &mut csr, options(nostack, preserves_flags));
+    }
+    csr
+}
+
+#[cfg(target_arch = "x86_64")]
+fn write_mxcsr(csr: u32) {
+    unsafe {
+        core::arch::asm!("ldmxcsr [{}]", in(reg) &csr, options(nostack, readonly, preserves_flags));
+    }
+}
+
+/// Calculate mean using portable SIMD (`std::simd`), which runs on any target
+#[cfg(feature = "portable-simd")]
+pub fn calculate_mean_portable_simd(data: &[f32]) -> f32 {
+    let chunks = data.chunks_exact(8);
+    let remainder = chunks.remainder();
+
+    let sum = chunks.fold(f32x8::splat(0.0), |acc, chunk| acc + f32x8::from_slice(chunk));
+    let remainder_sum: f32 = remainder.iter().sum();
+
+    (sum.reduce_sum() + remainder_sum) / data.len() as f32
+}
+
+/// Calculate mean across threads, running the SIMD mean on each chunk
+///
+/// Each rayon task returns a partial (sum, count) pair; partials are combined
+/// in f64 so the final reduction doesn't add rounding error of its own.
+#[cfg(feature = "parallel")]
+pub fn calculate_mean_parallel(data: &[f32]) -> f32 {
+    // Large enough to amortize task overhead, small enough to balance across cores
+    const CHUNK_SIZE: usize = 1 << 16;
+
+    let (sum, count) = data
+        .par_chunks(CHUNK_SIZE)
+        .map(|chunk| {
+            let (sum, count) = simd_sum_count(chunk);
+            (sum as f64, count)
+        })
+        .reduce(|| (0.0, 0), |(sum_a, count_a), (sum_b, count_b)| (sum_a + sum_b, count_a + count_b));
+
+    (sum / count as f64) as f32
+}
+
+/// Mean of a JavaScript `Float32Array`, summed with the scalar loop
+///
+/// wasm-bindgen copies the array into linear memory for the `&[f32]`
+/// argument. An empty array gives NaN, like [`calculate_mean_scalar`].
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn mean_scalar_js(data: &[f32]) -> f32 {
+    calculate_mean_scalar(data)
+}
+
+/// Mean of a JavaScript `Float32Array` using [`calculate_mean_simd`]
+///
+/// Takes the SIMD128 path only when the module was compiled with
+/// `-C target-feature=+simd128`; otherwise this is the scalar mean.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn mean_simd_js(data: &[f32]) -> f32 {
+    calculate_mean_simd(data)
+}
+
+/// Mean of each column of `a` (reducing along axis 0)
+///
+/// An empty axis gives NaN means, like [`calculate_mean_simd`].
+#[cfg(feature = "ndarray")]
+pub fn mean_axis0(a: &ArrayView2<f32>) -> Array1<f32> {
+    // The columns of `a` are the rows of its transpose
+    row_means(a.t())
+}
+
+/// Mean of each row of `a` (reducing along axis 1)
+///
+/// An empty axis gives NaN means, like [`calculate_mean_simd`].
+#[cfg(feature = "ndarray")]
+pub fn mean_axis1(a: &ArrayView2<f32>) -> Array1<f32> {
+    row_means(a.view())
+}
+
+#[cfg(feature = "ndarray")]
+fn row_means(a: ArrayView2<f32>) -> Array1<f32> {
+    // Contiguous rows go straight to the SIMD mean
+    if a.ncols() <= 1 || a.strides()[1] == 1 {
+        return a
+            .rows()
+            .into_iter()
+            .map(|row| match row.as_slice() {
+                Some(row) => calculate_mean_simd(row),
+                None => row.sum() / row.len() as f32,
+            })
+            .collect();
+    }
+
+    // Otherwise add up whole columns, which are contiguous in column-major
+    // views, instead of striding through each row
+    let mut sums = Array1::zeros(a.nrows());
+    for column in a.columns() {
+        sums += &column;
+    }
+    let n = a.ncols() as f32;
+    sums.mapv_inplace(|sum: f32| sum / n);
+    sums
+}
+
+/// Whether `data` starts on a 32-byte boundary, as required by aligned AVX loads
+pub fn is_aligned_32(data: &[f32]) -> bool {
+    (data.as_ptr() as usize).is_multiple_of(32)
+}
+
+/// One 32-byte-aligned block of 8 floats, the unit of [`AlignedBuffer`] storage
+#[derive(Clone, Copy)]
+#[repr(C, align(32))]
+struct AlignedBlock([f32; 8]);
+
+/// Heap buffer of f32 whose first element is 32-byte aligned
+///
+/// Derefs to `[f32]`, so it can be passed anywhere a slice is expected.
+#[derive(Clone)]
+pub struct AlignedBuffer {
+    blocks: Vec<AlignedBlock>,
+    len: usize,
+}
+
+impl Deref for AlignedBuffer {
+    type Target = [f32];
+
+    fn deref(&self) -> &[f32] {
+        // The blocks are contiguous, padding-free arrays of f32 covering at least `len` elements
+        unsafe { core::slice::from_raw_parts(self.blocks.as_ptr().cast(), self.len) }
+    }
+}
+
+impl DerefMut for AlignedBuffer {
+    fn deref_mut(&mut self) -> &mut [f32] {
+        unsafe { core::slice::from_raw_parts_mut(self.blocks.as_mut_ptr().cast(), self.len) }
+    }
+}
+
+impl From<&[f32]> for AlignedBuffer {
+    fn from(data: &[f32]) -> Self {
+        let mut buffer = aligned_vec_f32(data.len());
+        buffer.copy_from_slice(data);
+        buffer
+    }
+}
+
+/// Allocate a zeroed, 32-byte-aligned buffer of `len` floats
+pub fn aligned_vec_f32(len: usize) -> AlignedBuffer {
+    AlignedBuffer {
+        blocks: vec![AlignedBlock([0.0; 8]); len.div_ceil(8)],
+        len,
+    }
+}
+
+/// Calculate mean using chunked approach (compiler auto-vectorization)
+///
+/// Generic over the float type, so the same fallback serves f32 and f64 data.
+/// Uses 8-element chunks; see [`calculate_mean_chunks_n`] for other widths.
+pub fn calculate_mean_chunks<T: Float + Sum>(data: &[T]) -> T {
+    calculate_mean_chunks_n::<8, T>(data)
+}
+
+/// Calculate mean using `N`-element chunks (compiler auto-vectorization)
+///
+/// The chunk width decides how far the compiler can vectorize each inner sum,
+/// so sweeping `N` shows which width suits a given CPU.
+pub fn calculate_mean_chunks_n<const N: usize, T: Float + Sum>(data: &[T]) -> T {
+    const { assert!(N > 0, "chunk size must be non-zero") };
+    let chunks = data.chunks_exact(N);
+    let remainder = chunks.remainder();
+    
+    let chunk_sum: T = chunks
+        .map(|chunk| chunk.iter().copied().sum::<T>())
+        .sum();
+    
+    let remainder_sum: T = remainder.iter().copied().sum();
+    
+    let len = T::from(data.len()).expect("slice length is representable as a float");
+    (chunk_sum + remainder_sum) / len
+}
+
+// Unit tests for private helpers; the public API is tested under `tests/`
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    #[cfg(target_arch = "x86_64")]
+    use super::*;
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn hsum256_adds_every_lane() {
+        if !std::is_x86_feature_detected!("avx") {
+            return;
+        }
+        let sum = unsafe { hsum256_ps(_mm256_set_ps(8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0)) };
+        assert_eq!(sum, 36.0);
+    }
+}
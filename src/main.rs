@@ -1,170 +1,1230 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use rand::prelude::*;
-use std::time::Instant;
+use rand::rngs::StdRng;
+use rand_distr::Normal;
+use std::fmt;
+use std::hint::black_box;
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use simd_poc::{
+    MeanFn, calculate_mean_chunks, calculate_mean_kahan, calculate_mean_pairwise, calculate_mean_scalar,
+    calculate_mean_scalar_wide, calculate_mean_simd, calculate_mean_simd_wide, is_aligned_32,
+    report_cpu_features, simd_max, simd_min, simd_summary, variance_one_pass, variance_two_pass,
+};
+
+#[cfg(feature = "portable-simd")]
+use simd_poc::calculate_mean_portable_simd;
+
+#[cfg(feature = "parallel")]
+use simd_poc::calculate_mean_parallel;
+
+#[cfg(target_arch = "x86_64")]
+use simd_poc::{
+    AlignedBuffer, calculate_mean_simd_avx, calculate_mean_simd_avx_aligned,
+    calculate_mean_simd_avx_single_acc, calculate_mean_simd_avx_unaligned,
+};
+
+#[cfg(target_arch = "aarch64")]
+use simd_poc::{calculate_mean_simd_neon, calculate_mean_simd_neon_single_acc};
 
 #[cfg(target_arch = "x86_64")]
-use std::arch::x86_64::*;
+use std::arch::x86_64::_mm256_loadu_ps;
+
+/// SIMD vs non-SIMD mean benchmarks; without a subcommand, runs `bench`
+#[derive(Parser)]
+#[command(name = "simd_poc", args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    bench: BenchArgs,
+    /// Print which SIMD features the mean dispatch detected, then exit
+    #[arg(long)]
+    features: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Time every mean implementation (the default)
+    Bench(BenchArgs),
+    /// Print each summation strategy's error against an f64 reference
+    Accuracy(DataArgs),
+    /// Time a load-only AVX loop as a streaming-read ceiling for the mean kernels
+    #[cfg(target_arch = "x86_64")]
+    Bandwidth(TimedArgs),
+    /// Time `simd_summary` against separate mean, variance, min and max passes
+    Summary(TimedArgs),
+}
+
+/// Options every subcommand shares: the generated data
+#[derive(Args)]
+struct DataArgs {
+    /// Comma-separated dataset sizes [default: 500,1000,50000,1000000,100000000]
+    #[arg(long, value_name = "N,N,...", value_delimiter = ',')]
+    sizes: Option<Vec<usize>>,
+    /// Range of the generated random floats, shorthand for `--distribution uniform:LO,HI`
+    #[arg(long, value_name = "LO:HI", value_parser = parse_range, conflicts_with = "distribution")]
+    range: Option<Distribution>,
+    /// Data distribution: uniform:LO,HI or normal:MEAN,STDDEV [default: uniform:20,100]
+    #[arg(long, value_name = "D", value_parser = parse_distribution)]
+    distribution: Option<Distribution>,
+    /// Seed for the data generator [default: random, printed at startup]
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+/// How often each implementation runs, for the subcommands that time them
+#[derive(Args)]
+struct TimingArgs {
+    /// Timed iterations per implementation [default: 100]
+    #[arg(long, value_name = "N", value_parser = parse_iters)]
+    iters: Option<usize>,
+    /// Untimed runs of each implementation before timing starts [default: 3]
+    #[arg(long, value_name = "N")]
+    warmup: Option<usize>,
+}
+
+impl DataArgs {
+    /// The default configuration with every option that was given applied
+    fn into_config(self) -> BenchmarkConfig {
+        let mut config = BenchmarkConfig::new();
+        if let Some(sizes) = self.sizes {
+            config = config.sizes(sizes);
+        }
+        if let Some(distribution) = self.range.or(self.distribution) {
+            config = config.distribution(distribution);
+        }
+        if let Some(seed) = self.seed {
+            config = config.seed(seed);
+        }
+        config
+    }
+}
+
+impl TimingArgs {
+    /// `config` with the iteration counts that were given applied
+    fn apply(self, mut config: BenchmarkConfig) -> BenchmarkConfig {
+        if let Some(iters) = self.iters {
+            config = config.iters(iters);
+        }
+        if let Some(warmup) = self.warmup {
+            config = config.warmup(warmup);
+        }
+        config
+    }
+}
+
+/// Data and timing options of the subcommands that only print timings
+#[derive(Args)]
+struct TimedArgs {
+    #[command(flatten)]
+    data: DataArgs,
+    #[command(flatten)]
+    timing: TimingArgs,
+}
+
+impl TimedArgs {
+    fn into_config(self) -> BenchmarkConfig {
+        self.timing.apply(self.data.into_config())
+    }
+}
+
+/// Options of the classic benchmark table
+#[derive(Args)]
+struct BenchArgs {
+    #[command(flatten)]
+    data: DataArgs,
+    #[command(flatten)]
+    timing: TimingArgs,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+    /// Print one-pass vs two-pass variance instead of timings
+    #[arg(long)]
+    compare_variance: bool,
+    /// Time the parallel mean with each rayon thread count instead
+    #[cfg(feature = "parallel")]
+    #[arg(long, value_name = "N,N,...", value_delimiter = ',', value_parser = parse_thread_count)]
+    threads: Option<Vec<usize>>,
+    /// Print each benchmark buffer's address modulo 32 to stderr
+    #[arg(long)]
+    report_alignment: bool,
+}
+
+/// Benchmark parameters, assembled with builder methods
+///
+/// `run` yields one [`BenchmarkRun`] per size, so the sweep can be driven
+/// without going through the command line.
+#[derive(Clone)]
+struct BenchmarkConfig {
+    sizes: Vec<usize>,
+    warmup: usize,
+    iters: usize,
+    distribution: Distribution,
+    seed: u64,
+    report_alignment: bool,
+    progress: bool,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        BenchmarkConfig {
+            sizes: vec![500, 1000, 50000, 1000000, 100000000],
+            warmup: 3,
+            iters: 100,
+            distribution: Distribution::Uniform(20.0, 100.0),
+            // Resolved up front and printed, so every run can be reproduced
+            seed: rand::random(),
+            report_alignment: false,
+            progress: false,
+        }
+    }
+}
+
+impl BenchmarkConfig {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn sizes(mut self, sizes: Vec<usize>) -> Self {
+        self.sizes = sizes;
+        self
+    }
+
+    /// Untimed runs of each implementation before timing starts
+    fn warmup(mut self, warmup: usize) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    fn iters(mut self, iters: usize) -> Self {
+        self.iters = iters;
+        self
+    }
+
+    fn distribution(mut self, distribution: Distribution) -> Self {
+        self.distribution = distribution;
+        self
+    }
+
+    fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Report whether each generated buffer qualifies for aligned AVX loads
+    fn report_alignment(mut self, report_alignment: bool) -> Self {
+        self.report_alignment = report_alignment;
+        self
+    }
+
+    /// Show which size is being benchmarked on stdout, overwritten once it finishes
+    fn progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// A data generator seeded with `seed`
+    fn rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed)
+    }
+
+    /// Benchmark every implementation at each size, in order
+    ///
+    /// Each size is only benchmarked when the iterator reaches it, so rows can
+    /// be printed as they finish.
+    fn run(&self) -> impl Iterator<Item = BenchmarkRun> + '_ {
+        let mut rng = self.rng();
+        self.sizes.iter().map(move |&size| {
+            if self.progress {
+                print!("Benchmarking size {}...", format_size(size));
+                let _ = io::stdout().flush();
+            }
+            let results = self.benchmark_size(size, &mut rng);
+            if self.progress {
+                // Return to the start of the line and erase it for the row that follows
+                print!("\r\x1b[2K");
+            }
+            BenchmarkRun { size, results }
+        })
+    }
+
+    fn benchmark_size(&self, size: usize, rng: &mut StdRng) -> BenchmarkResults {
+        let data = black_box(generate_data(size, self.distribution, rng));
+
+        // On stderr, so machine-readable output formats stay intact
+        if self.report_alignment {
+            let offset = data.as_ptr() as usize % 32;
+            eprintln!("Size {}: address % 32 = {} ({})",
+                format_size(size),
+                offset,
+                if is_aligned_32(&data) { "aligned loads" } else { "unaligned loads" });
+        }
+        
+        // Warmup runs
+        for _ in 0..self.warmup {
+            let _ = calculate_mean_scalar(&data);
+            let _ = calculate_mean_simd(&data);
+            let _ = calculate_mean_chunks(&data);
+            let _ = calculate_mean_kahan(&data);
+            #[cfg(feature = "portable-simd")]
+            let _ = calculate_mean_portable_simd(&data);
+            #[cfg(feature = "parallel")]
+            let _ = calculate_mean_parallel(&data);
+        }
+        
+        // Benchmark scalar implementation
+        let (scalar_mean, scalar_time) = time_iterations(self.iters, &data, calculate_mean_scalar);
+        
+        // Benchmark SIMD implementation
+        let (simd_mean, simd_time) = time_iterations(self.iters, &data, calculate_mean_simd);
+        
+        // Benchmark using chunks
+        let (chunk_mean, chunk_time) = time_iterations(self.iters, &data, calculate_mean_chunks);
+        
+        // Benchmark compensated summation
+        let (kahan_mean, kahan_time) = time_iterations(self.iters, &data, calculate_mean_kahan);
+        
+        // Benchmark portable SIMD
+        #[cfg(feature = "portable-simd")]
+        let (portable_mean, portable_time) = time_iterations(self.iters, &data, calculate_mean_portable_simd);
+        
+        // Benchmark multi-threaded SIMD
+        #[cfg(feature = "parallel")]
+        let (parallel_mean, parallel_time) = time_iterations(self.iters, &data, calculate_mean_parallel);
+        
+        BenchmarkResults {
+            scalar_mean,
+            simd_mean,
+            chunk_mean,
+            kahan_mean,
+            #[cfg(feature = "portable-simd")]
+            portable_mean,
+            #[cfg(feature = "parallel")]
+            parallel_mean,
+            scalar_time,
+            simd_time,
+            chunk_time,
+            kahan_time,
+            #[cfg(feature = "portable-simd")]
+            portable_time,
+            #[cfg(feature = "parallel")]
+            parallel_time,
+        }
+    }
+}
+
+/// Distribution the benchmark data is drawn from
+#[derive(Clone, Copy, PartialEq)]
+enum Distribution {
+    /// Uniform over the half-open range `lo..hi`
+    Uniform(f32, f32),
+    Normal { mean: f32, stddev: f32 },
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Distribution::Uniform(lo, hi) => write!(f, "uniform({}, {})", lo, hi),
+            Distribution::Normal { mean, stddev } => write!(f, "normal(mean {}, stddev {})", mean, stddev),
+        }
+    }
+}
+
+/// How benchmark results are printed
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Table,
+    Csv,
+    Markdown,
+    /// Serialized [`BenchmarkRun`]s, behind the `serde` feature
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+/// Parse `uniform:LO,HI` or `normal:MEAN,STDDEV`
+fn parse_distribution(value: &str) -> Result<Distribution, String> {
+    let invalid = || format!("invalid distribution '{}', expected uniform:LO,HI or normal:MEAN,STDDEV", value);
+    let (kind, params) = value.split_once(':').ok_or_else(invalid)?;
+    let (a, b) = params.split_once(',').ok_or_else(invalid)?;
+
+    match kind.trim() {
+        "uniform" => parse_uniform(a, b),
+        "normal" => {
+            let mean: f32 = a.trim().parse().map_err(|_| format!("invalid mean '{}'", a))?;
+            let stddev: f32 = b.trim().parse().map_err(|_| format!("invalid stddev '{}'", b))?;
+            if !mean.is_finite() || !stddev.is_finite() || stddev <= 0.0 {
+                return Err(format!("invalid distribution '{}', STDDEV must be positive", value));
+            }
+            Ok(Distribution::Normal { mean, stddev })
+        }
+        _ => Err(invalid()),
+    }
+}
+
+fn parse_uniform(lo: &str, hi: &str) -> Result<Distribution, String> {
+    let lo_value: f32 = lo.trim().parse().map_err(|_| format!("invalid range bound '{}'", lo))?;
+    let hi_value: f32 = hi.trim().parse().map_err(|_| format!("invalid range bound '{}'", hi))?;
+    if !lo_value.is_finite() || !hi_value.is_finite() || lo_value >= hi_value {
+        return Err(format!("invalid range '{}:{}', LO must be less than HI", lo, hi));
+    }
+    Ok(Distribution::Uniform(lo_value, hi_value))
+}
+
+/// Parse `LO:HI` as a uniform distribution
+fn parse_range(value: &str) -> Result<Distribution, String> {
+    let (lo, hi) = value
+        .split_once(':')
+        .ok_or_else(|| format!("invalid range '{}', expected LO:HI", value))?;
+    parse_uniform(lo, hi)
+}
+
+fn parse_iters(value: &str) -> Result<usize, String> {
+    match value.trim().parse() {
+        Ok(iters) if iters > 0 => Ok(iters),
+        _ => Err(format!("invalid iteration count '{}'", value)),
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn parse_thread_count(value: &str) -> Result<usize, String> {
+    match value.trim().parse() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!("invalid thread count '{}'", value)),
+    }
+}
 
 fn main() {
+    let cli = Cli::parse();
+
+    if cli.features {
+        print_cpu_features();
+        return;
+    }
+
+    match cli.command.unwrap_or(Command::Bench(cli.bench)) {
+        Command::Bench(args) => run_bench(args),
+        Command::Accuracy(args) => print_accuracy_report(&args.into_config()),
+        #[cfg(target_arch = "x86_64")]
+        Command::Bandwidth(args) => print_bandwidth_baseline(&args.into_config()),
+        Command::Summary(args) => print_summary_comparison(&args.into_config()),
+    }
+}
+
+/// Print the table behind `--features`, for bug reports
+fn print_cpu_features() {
+    println!("CPU Features");
+    println!("============");
+    println!("{:<12} {:<12}", "Feature", "Available");
+    println!("{}", "-".repeat(24));
+
+    for (name, available) in report_cpu_features() {
+        println!("{:<12} {:<12}", name, if available { "yes" } else { "no" });
+    }
+}
+
+/// Run the `bench` subcommand: the timing table in the chosen format, or one of its alternatives
+fn run_bench(args: BenchArgs) {
+    // Progress lines would only be noise in piped or redirected output, and
+    // would corrupt the machine-readable formats even on a terminal
+    let config = args
+        .timing
+        .apply(args.data.into_config())
+        .report_alignment(args.report_alignment)
+        .progress(io::stdout().is_terminal() && args.format == OutputFormat::Table);
+    let config = &config;
+
+    if args.compare_variance {
+        print_variance_comparison(config);
+        return;
+    }
+
+    #[cfg(feature = "parallel")]
+    if let Some(threads) = &args.threads {
+        print_thread_sweep(config, threads);
+        return;
+    }
+
+    match args.format {
+        OutputFormat::Table => print_table(config),
+        OutputFormat::Csv => {
+            // Keep stdout machine-readable; the seed still goes to stderr
+            eprintln!("Seed: {}", config.seed);
+            print_csv(config);
+        }
+        OutputFormat::Markdown => {
+            eprintln!("Seed: {}", config.seed);
+            print_markdown(config);
+        }
+        #[cfg(feature = "serde")]
+        OutputFormat::Json => {
+            eprintln!("Seed: {}", config.seed);
+            print_json(config);
+        }
+    }
+}
+
+/// Print the human-readable benchmark table followed by the comparison sections
+fn print_table(config: &BenchmarkConfig) {
     println!("SIMD vs Non-SIMD Mean Calculation Benchmark");
     println!("============================================");
-    
-    // Test different sizes
-    let sizes = [500, 1000, 50000, 1000000, 100000000];
+    println!("Seed: {}", config.seed);
+    println!("Iterations: {}", config.iters);
+    println!("Distribution: {}", config.distribution);
     
     // Print table header
-    println!("{:<12} {:<15} {:<15} {:<15} {:<12} {:<12} {:<12}", 
-        "Size", "Scalar (ns)", "SIMD (ns)", "Chunk (ns)", 
-        "SIMD Speed", "Chunk Speed", "Accuracy");
-    println!("{}", "-".repeat(95));
-    
-    for &size in &sizes {
-        let results = benchmark_size(size);
-        
-        // Calculate speedups
-        let scalar_ns = results.scalar_time.as_nanos() as f64;
-        let simd_ns = results.simd_time.as_nanos() as f64;
-        let chunk_ns = results.chunk_time.as_nanos() as f64;
-        
-        let simd_speedup = if simd_ns > 0.0 { scalar_ns / simd_ns } else { 0.0 };
-        let chunk_speedup = if chunk_ns > 0.0 { scalar_ns / chunk_ns } else { 0.0 };
-        
-        // Calculate max difference for accuracy
-        let max_diff = (results.scalar_mean - results.simd_mean).abs()
-            .max((results.scalar_mean - results.chunk_mean).abs());
-        
-        println!("{:<12} {:<15} {:<15} {:<15}x {:<12.2}x {:<12.2}x {:<12.2e}", 
+    print!("{:<12} {:<21} {:<21} {:<21} {:<21} {:<12} {:<12} {:<12} {:<12} {:<12} {:<12} {:<12} {:<12} {:<12}", 
+        "Size", "Scalar (ns)", "SIMD (ns)", "Chunk (ns)", "Kahan (ns)", 
+        "SIMD Speed", "Chunk Speed", "Kahan Speed", "Accuracy", "Kahan Diff",
+        "Scalar GB/s", "SIMD GB/s", "Chunk GB/s", "Kahan GB/s");
+    #[cfg(feature = "portable-simd")]
+    print!(" {:<21} {:<12}", "Portable (ns)", "Port. GB/s");
+    #[cfg(feature = "parallel")]
+    print!(" {:<21} {:<12}", "Parallel (ns)", "Par. GB/s");
+    println!(" {:<12}", "Max CV");
+    let extra_columns = cfg!(feature = "portable-simd") as usize + cfg!(feature = "parallel") as usize;
+    println!("{}", "-".repeat(225 + 35 * extra_columns));
+
+    let mut unstable_sizes = Vec::new();
+    for BenchmarkRun { size, results } in config.run() {
+        print!("{:<12} {:<21} {:<21} {:<21} {:<21}x {:<12.2}x {:<12.2}x {:<12.2}x {:<12.2e} {:<12.2e} {:<12.2} {:<12.2} {:<12.2} {:<12.2}", 
             format_size(size),
-            scalar_ns as u64,
-            simd_ns as u64,
-            chunk_ns as u64,
-            simd_speedup,
-            chunk_speedup,
-            max_diff);
+            format_timing(&results.scalar_time),
+            format_timing(&results.simd_time),
+            format_timing(&results.chunk_time),
+            format_timing(&results.kahan_time),
+            results.speedup(&results.simd_time),
+            results.speedup(&results.chunk_time),
+            results.speedup(&results.kahan_time),
+            results.max_diff(),
+            results.kahan_diff(),
+            throughput_gbps(size, &results.scalar_time),
+            throughput_gbps(size, &results.simd_time),
+            throughput_gbps(size, &results.chunk_time),
+            throughput_gbps(size, &results.kahan_time));
+        #[cfg(feature = "portable-simd")]
+        print!(" {:<21} {:<12.2}", format_timing(&results.portable_time), throughput_gbps(size, &results.portable_time));
+        #[cfg(feature = "parallel")]
+        print!(" {:<21} {:<12.2}", format_timing(&results.parallel_time), throughput_gbps(size, &results.parallel_time));
+        let max_cv = results.max_cv();
+        println!(" {:<12}", format!("{:.1}%", 100.0 * max_cv));
+        if max_cv > CV_WARNING_THRESHOLD {
+            unstable_sizes.push(format_size(size));
+        }
+    }
+
+    if !unstable_sizes.is_empty() {
+        println!();
+        println!("Warning: timings at size {} varied by more than {:.0}% between iterations;",
+            unstable_sizes.join(", "),
+            100.0 * CV_WARNING_THRESHOLD);
+        println!("rerun with more --iters (or --warmup) before trusting them.");
     }
     
     println!();
     println!("Legend:");
+    println!("- Timings: min/median over {} iterations after {} warmup runs; speedups and comparison tables use the minimum", config.iters, config.warmup);
     println!("- SIMD Speed: Speedup factor of SIMD vs Scalar");
     println!("- Chunk Speed: Speedup factor of Chunks vs Scalar");
+    println!("- Kahan Speed: Speedup factor of Kahan summation vs Scalar");
     println!("- Accuracy: Maximum difference between implementations");
+    println!("- Kahan Diff: Difference between compensated (Kahan) and Scalar means");
+    println!("- GB/s: Input bytes read per second at the minimum time; converging values at large sizes mean memory bandwidth is the limit");
+    #[cfg(feature = "portable-simd")]
+    println!("- Portable: std::simd implementation (portable-simd feature)");
+    #[cfg(feature = "parallel")]
+    println!("- Parallel: multi-threaded SIMD mean using rayon (parallel feature)");
+    println!("- Max CV: Largest coefficient of variation (stddev / mean) of any implementation's iterations");
+    println!("- Inputs and results pass through std::hint::black_box so no measured call is optimized away");
+    println!();
+    println!("This table is a quick smoke test; run `cargo bench` for statistically sound numbers.");
+
+    // The comparison sections draw fresh data from their own seeded generator
+    let mut rng = config.rng();
+
+    print_wide_accumulator_comparison(config, &mut rng);
+
+    #[cfg(target_arch = "x86_64")]
+    print_avx_accumulator_comparison(config, &mut rng);
+
+    #[cfg(target_arch = "x86_64")]
+    print_alignment_comparison(config, &mut rng);
+
+    #[cfg(target_arch = "aarch64")]
+    print_neon_accumulator_comparison(config, &mut rng);
 }
 
-struct BenchmarkResults {
-    scalar_mean: f32,
-    simd_mean: f32,
-    chunk_mean: f32,
-    scalar_time: std::time::Duration,
-    simd_time: std::time::Duration,
-    chunk_time: std::time::Duration,
+/// Print one raw CSV row per size, for regression tracking
+fn print_csv(config: &BenchmarkConfig) {
+    println!("size,scalar_ns,simd_ns,chunk_ns,simd_speedup,chunk_speedup,max_diff");
+
+    for BenchmarkRun { size, results } in config.run() {
+        println!("{},{},{},{},{},{},{}",
+            size,
+            results.scalar_time.min.as_nanos(),
+            results.simd_time.min.as_nanos(),
+            results.chunk_time.min.as_nanos(),
+            results.speedup(&results.simd_time),
+            results.speedup(&results.chunk_time),
+            results.max_diff());
+    }
 }
 
-fn benchmark_size(size: usize) -> BenchmarkResults {
-    // Generate random floats between 20 and 100
-    let mut rng = thread_rng();
-    let data: Vec<f32> = (0..size)
-        .map(|_| rng.gen_range(20.0..100.0))
-        .collect();
-    
-    // Warmup runs
-    for _ in 0..3 {
-        let _ = calculate_mean_scalar(&data);
-        let _ = calculate_mean_simd(&data);
-        let _ = calculate_mean_chunks(&data);
+/// Print the benchmark table as a GitHub-flavored markdown table
+fn print_markdown(config: &BenchmarkConfig) {
+    let headers = [
+        "Size", "Scalar (ns)", "SIMD (ns)", "Chunk (ns)", "Kahan (ns)",
+        "SIMD Speed", "Chunk Speed", "Kahan Speed", "Accuracy", "Kahan Diff",
+        "Scalar GB/s", "SIMD GB/s", "Chunk GB/s", "Kahan GB/s",
+        #[cfg(feature = "portable-simd")]
+        "Portable (ns)",
+        #[cfg(feature = "portable-simd")]
+        "Portable GB/s",
+        #[cfg(feature = "parallel")]
+        "Parallel (ns)",
+        #[cfg(feature = "parallel")]
+        "Parallel GB/s",
+    ];
+
+    println!("| {} |", headers.join(" | "));
+    println!("|{}", "---|".repeat(headers.len()));
+
+    for BenchmarkRun { size, results } in config.run() {
+        let cells = [
+            format_size(size),
+            format_timing(&results.scalar_time),
+            format_timing(&results.simd_time),
+            format_timing(&results.chunk_time),
+            format_timing(&results.kahan_time),
+            format!("{:.2}x", results.speedup(&results.simd_time)),
+            format!("{:.2}x", results.speedup(&results.chunk_time)),
+            format!("{:.2}x", results.speedup(&results.kahan_time)),
+            format!("{:.2e}", results.max_diff()),
+            format!("{:.2e}", results.kahan_diff()),
+            format!("{:.2}", throughput_gbps(size, &results.scalar_time)),
+            format!("{:.2}", throughput_gbps(size, &results.simd_time)),
+            format!("{:.2}", throughput_gbps(size, &results.chunk_time)),
+            format!("{:.2}", throughput_gbps(size, &results.kahan_time)),
+            #[cfg(feature = "portable-simd")]
+            format_timing(&results.portable_time),
+            #[cfg(feature = "portable-simd")]
+            format!("{:.2}", throughput_gbps(size, &results.portable_time)),
+            #[cfg(feature = "parallel")]
+            format_timing(&results.parallel_time),
+            #[cfg(feature = "parallel")]
+            format!("{:.2}", throughput_gbps(size, &results.parallel_time)),
+        ];
+
+        println!("| {} |", cells.join(" | "));
     }
-    
-    // Benchmark scalar implementation
-    let start = Instant::now();
-    let scalar_mean = calculate_mean_scalar(&data);
-    let scalar_time = start.elapsed();
-    
-    // Benchmark SIMD implementation
-    let start = Instant::now();
-    let simd_mean = calculate_mean_simd(&data);
-    let simd_time = start.elapsed();
-    
-    // Benchmark using chunks
-    let start = Instant::now();
-    let chunk_mean = calculate_mean_chunks(&data);
-    let chunk_time = start.elapsed();
-    
-    BenchmarkResults {
-        scalar_mean,
-        simd_mean,
-        chunk_mean,
-        scalar_time,
-        simd_time,
-        chunk_time,
+}
+
+/// Print every run as a JSON array, with durations in nanoseconds
+#[cfg(feature = "serde")]
+fn print_json(config: &BenchmarkConfig) {
+    let json = serde_json::to_string_pretty(&config.run().collect::<Vec<_>>()).expect("benchmark results serialize to JSON");
+    println!("{}", json);
+}
+
+/// Compare one-pass and two-pass variance against an f64 two-pass reference
+fn print_variance_comparison(config: &BenchmarkConfig) {
+    let mut rng = config.rng();
+
+    println!("One-Pass vs Two-Pass Variance Comparison");
+    println!("========================================");
+    println!("Seed: {}", config.seed);
+    println!("Distribution: {}", config.distribution);
+    println!("{:<12} {:<15} {:<15} {:<15} {:<12} {:<12}",
+        "Size", "One-Pass", "Two-Pass", "Reference", "One Error", "Two Error");
+    println!("{}", "-".repeat(86));
+
+    for &size in &config.sizes {
+        let data = generate_data(size, config.distribution, &mut rng);
+        let mean = data.iter().map(|&x| x as f64).sum::<f64>() / size as f64;
+        let reference = data.iter().map(|&x| (x as f64 - mean).powi(2)).sum::<f64>() / size as f64;
+
+        let one_pass = variance_one_pass(&data);
+        let two_pass = variance_two_pass(&data);
+
+        println!("{:<12} {:<15.6e} {:<15.6e} {:<15.6e} {:<12.2e} {:<12.2e}",
+            format_size(size),
+            one_pass,
+            two_pass,
+            reference,
+            (one_pass as f64 - reference).abs(),
+            (two_pass as f64 - reference).abs());
+    }
+
+    println!();
+    println!("One-pass computes E[x^2] - E[x]^2 and loses precision to cancellation when");
+    println!("the mean is large relative to the spread; try --distribution normal:10000,1.");
+}
+
+/// Time the parallel mean on the largest size with a rayon pool of each thread count
+#[cfg(feature = "parallel")]
+fn print_thread_sweep(config: &BenchmarkConfig, threads: &[usize]) {
+    let mut rng = config.rng();
+    let size = config.sizes.iter().copied().max().unwrap_or(0);
+    let data = black_box(generate_data(size, config.distribution, &mut rng));
+
+    println!("Parallel Mean Thread Scaling");
+    println!("============================");
+    println!("Seed: {}", config.seed);
+    println!("Iterations: {}", config.iters);
+    println!("Distribution: {}", config.distribution);
+    println!("Size: {}", format_size(size));
+    println!("{:<12} {:<21} {:<12} {:<12}", "Threads", "Parallel (ns)", "Speedup", "GB/s");
+    println!("{}", "-".repeat(60));
+
+    let time_with_threads = |count: usize| {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(count)
+            .build()
+            .expect("failed to build the rayon thread pool");
+        pool.install(|| {
+            // Warmup runs
+            for _ in 0..config.warmup {
+                let _ = calculate_mean_parallel(&data);
+            }
+            time_iterations(config.iters, &data, calculate_mean_parallel).1
+        })
+    };
+
+    let single_ns = time_with_threads(1).min.as_nanos() as f64;
+
+    for &count in threads {
+        let timing = time_with_threads(count);
+        let ns = timing.min.as_nanos() as f64;
+        let speedup = if ns > 0.0 { single_ns / ns } else { 0.0 };
+        println!("{:<12} {:<21} {:<12.2}x {:<12.2}",
+            count,
+            format_timing(&timing),
+            speedup,
+            throughput_gbps(size, &timing));
     }
+
+    println!();
+    println!("Speedup is relative to a single-threaded pool at the minimum time. Once GB/s");
+    println!("stops growing with threads, memory bandwidth is the limit.");
 }
 
-fn format_size(size: usize) -> String {
-    match size {
-        n if n >= 1_000_000 => format!("{}M", n / 1_000_000),
-        n if n >= 1_000 => format!("{}K", n / 1_000),
-        n => n.to_string(),
+/// Summation strategies compared by the `accuracy` subcommand
+const ACCURACY_STRATEGIES: [(&str, MeanFn); 6] = [
+    ("Naive", calculate_mean_scalar),
+    ("Pairwise", calculate_mean_pairwise),
+    ("SIMD", calculate_mean_simd),
+    ("Chunked", calculate_mean_chunks),
+    ("Kahan", calculate_mean_kahan),
+    ("f64 Accumulator", calculate_mean_scalar_wide),
+];
+
+/// Print the absolute and relative error of each summation strategy against an f64 reference
+fn print_accuracy_report(config: &BenchmarkConfig) {
+    let mut rng = config.rng();
+
+    println!("Summation Accuracy Report");
+    println!("=========================");
+    println!("Seed: {}", config.seed);
+    println!("Distribution: {}", config.distribution);
+
+    for &size in &config.sizes {
+        let data = generate_data(size, config.distribution, &mut rng);
+        let reference = data.iter().map(|&x| x as f64).sum::<f64>() / size as f64;
+
+        println!();
+        println!("Size {} (reference mean {:.9e}, summed in f64)", format_size(size), reference);
+        println!("{:<18} {:<18} {:<12} {:<12}", "Strategy", "Mean", "Abs Error", "Rel Error");
+        println!("{}", "-".repeat(63));
+
+        for (name, mean) in ACCURACY_STRATEGIES {
+            let mean = mean(&data);
+            let abs_error = (mean as f64 - reference).abs();
+            let rel_error = if reference != 0.0 { abs_error / reference.abs() } else { abs_error };
+            println!("{:<18} {:<18.9e} {:<12.2e} {:<12.2e}", name, mean, abs_error, rel_error);
+        }
     }
+
+    println!();
+    println!("Errors are of the mean; up to the rounding of the final division, the relative");
+    println!("error is that of the sum.");
 }
 
-/// Calculate mean using scalar operations
-fn calculate_mean_scalar(data: &[f32]) -> f32 {
-    let sum: f32 = data.iter().sum();
-    sum / data.len() as f32
+/// Time `simd_summary` against computing its statistics in four separate passes
+fn print_summary_comparison(config: &BenchmarkConfig) {
+    let mut rng = config.rng();
+
+    println!("One-Pass Summary vs Separate Passes Benchmark");
+    println!("=============================================");
+    println!("Seed: {}", config.seed);
+    println!("Iterations: {}", config.iters);
+    println!("Distribution: {}", config.distribution);
+    println!("{:<12} {:<21} {:<21} {:<12} {:<12} {:<12}",
+        "Size", "Summary (ns)", "Separate (ns)", "Speedup", "Summary GB/s", "Sep. GB/s");
+    println!("{}", "-".repeat(95));
+
+    // Fold every statistic into the returned value so none of them is dead code
+    let summary = |data: &[f32]| {
+        simd_summary(data).map_or(0.0, |s| s.mean + s.variance + s.min + s.max)
+    };
+    let separate = |data: &[f32]| {
+        calculate_mean_simd(data)
+            + variance_one_pass(data)
+            + simd_min(data).unwrap_or(0.0)
+            + simd_max(data).unwrap_or(0.0)
+    };
+
+    for &size in &config.sizes {
+        let data = black_box(generate_data(size, config.distribution, &mut rng));
+
+        // Warmup runs
+        for _ in 0..config.warmup {
+            let _ = summary(&data);
+            let _ = separate(&data);
+        }
+
+        let (_, summary_time) = time_iterations(config.iters, &data, summary);
+        let (_, separate_time) = time_iterations(config.iters, &data, separate);
+
+        let summary_ns = summary_time.min.as_nanos() as f64;
+        let speedup = if summary_ns > 0.0 { separate_time.min.as_nanos() as f64 / summary_ns } else { 0.0 };
+
+        println!("{:<12} {:<21} {:<21} {:<12.2}x {:<12.2} {:<12.2}",
+            format_size(size),
+            format_timing(&summary_time),
+            format_timing(&separate_time),
+            speedup,
+            throughput_gbps(size, &summary_time),
+            throughput_gbps(size, &separate_time));
+    }
+
+    println!();
+    println!("Timings are min/median; Speedup is Separate over Summary at the minimum.");
+    println!("Separate runs calculate_mean_simd, variance_one_pass, simd_min and simd_max,");
+    println!("reading the data four times where the summary reads it once.");
 }
 
-/// Calculate mean using SIMD operations (AVX on x86_64)
+/// Time an AVX loop that only loads the data, against the SIMD mean over the same buffer
 #[cfg(target_arch = "x86_64")]
-fn calculate_mean_simd(data: &[f32]) -> f32 {
-    if is_x86_feature_detected!("avx") {
-        unsafe { calculate_mean_simd_avx(data) }
-    } else {
-        calculate_mean_scalar(data)
+fn print_bandwidth_baseline(config: &BenchmarkConfig) {
+    if !is_x86_feature_detected!("avx") {
+        println!("AVX not available; the bandwidth baseline needs it");
+        return;
     }
-}
 
-#[cfg(not(target_arch = "x86_64"))]
-fn calculate_mean_simd(data: &[f32]) -> f32 {
-    calculate_mean_chunks(data)
+    let mut rng = config.rng();
+
+    println!("Streaming-Read Bandwidth Baseline");
+    println!("=================================");
+    println!("Seed: {}", config.seed);
+    println!("Iterations: {}", config.iters);
+    println!("{:<12} {:<21} {:<21} {:<12} {:<12} {:<12}",
+        "Size", "Loads (ns)", "SIMD Mean (ns)", "Load GB/s", "Mean GB/s", "Of Ceiling");
+    println!("{}", "-".repeat(95));
+
+    // Returning a constant keeps the closure usable with time_iterations
+    let loads = |data: &[f32]| {
+        unsafe { stream_loads_avx(data) };
+        0.0
+    };
+
+    for &size in &config.sizes {
+        let buffer = AlignedBuffer::from(generate_data(size, config.distribution, &mut rng).as_slice());
+        let data = black_box(&buffer[..]);
+
+        // Warmup runs
+        for _ in 0..config.warmup {
+            loads(data);
+            let _ = calculate_mean_simd(data);
+        }
+
+        let (_, load_time) = time_iterations(config.iters, data, loads);
+        let (_, mean_time) = time_iterations(config.iters, data, calculate_mean_simd);
+
+        let load_gbps = throughput_gbps(size, &load_time);
+        let mean_gbps = throughput_gbps(size, &mean_time);
+        let ceiling = if load_gbps > 0.0 { 100.0 * mean_gbps / load_gbps } else { 0.0 };
+
+        println!("{:<12} {:<21} {:<21} {:<12.2} {:<12.2} {:<12}",
+            format_size(size),
+            format_timing(&load_time),
+            format_timing(&mean_time),
+            load_gbps,
+            mean_gbps,
+            format!("{:.1}%", ceiling));
+    }
+
+    println!();
+    println!("Loads reads the 32-byte-aligned buffer with _mm256_loadu_ps and discards every");
+    println!("vector through black_box, so it is the read ceiling for a one-pass kernel. Once");
+    println!("the data outgrows the caches, Of Ceiling shows how close the mean gets to it.");
 }
 
+/// Load every full 8-float chunk of `data` and do nothing with it
+///
+/// # Safety
+///
+/// The CPU must support avx.
 #[cfg(target_arch = "x86_64")]
 #[target_feature(enable = "avx")]
-unsafe fn calculate_mean_simd_avx(data: &[f32]) -> f32 {
-    let mut sum = _mm256_setzero_ps();
+unsafe fn stream_loads_avx(data: &[f32]) {
     let mut i = 0;
-    
+
     // Process 8 floats at a time using AVX
     while i + 8 <= data.len() {
         unsafe {
-            let chunk = _mm256_loadu_ps(data.as_ptr().add(i));
-            sum = _mm256_add_ps(sum, chunk);
+            black_box(_mm256_loadu_ps(data.as_ptr().add(i)));
         }
         i += 8;
     }
-    
-    // Extract the sum from the AVX register
-    let mut result = [0.0f32; 8];
-    unsafe {
-        _mm256_storeu_ps(result.as_mut_ptr(), sum);
+}
+
+/// Compare f32 and f64 accumulators against an f64 reference mean
+fn print_wide_accumulator_comparison(config: &BenchmarkConfig, rng: &mut StdRng) {
+    println!();
+    println!("Wide (f64) Accumulator Comparison");
+    println!("{:<12} {:<15} {:<15} {:<15} {:<12} {:<12}",
+        "Size", "SIMD (ns)", "Wide (ns)", "SIMD Wide (ns)", "SIMD Error", "Wide Error");
+    println!("{}", "-".repeat(86));
+
+    for &size in &config.sizes {
+        let data = black_box(generate_data(size, config.distribution, rng));
+        let reference = data.iter().map(|&x| x as f64).sum::<f64>() / size as f64;
+
+        // Warmup runs
+        for _ in 0..config.warmup {
+            let _ = calculate_mean_simd(&data);
+            let _ = calculate_mean_scalar_wide(&data);
+            let _ = calculate_mean_simd_wide(&data);
+        }
+
+        let (simd_mean, simd_time) = time_iterations(config.iters, &data, calculate_mean_simd);
+        let (wide_mean, wide_time) = time_iterations(config.iters, &data, calculate_mean_scalar_wide);
+        let (simd_wide_mean, simd_wide_time) = time_iterations(config.iters, &data, calculate_mean_simd_wide);
+
+        // Report the worse of the two wide variants against the reference
+        let wide_error = (wide_mean as f64 - reference).abs()
+            .max((simd_wide_mean as f64 - reference).abs());
+
+        println!("{:<12} {:<15} {:<15} {:<15} {:<12.2e} {:<12.2e}",
+            format_size(size),
+            simd_time.min.as_nanos(),
+            wide_time.min.as_nanos(),
+            simd_wide_time.min.as_nanos(),
+            (simd_mean as f64 - reference).abs(),
+            wide_error);
     }
-    let simd_sum: f32 = result.iter().sum();
-    
-    // Handle remaining elements
-    let remaining_sum: f32 = data[i..].iter().sum();
-    
-    (simd_sum + remaining_sum) / data.len() as f32
 }
 
-/// Calculate mean using chunked approach (compiler auto-vectorization)
-fn calculate_mean_chunks(data: &[f32]) -> f32 {
-    const CHUNK_SIZE: usize = 8;
-    let chunks = data.chunks_exact(CHUNK_SIZE);
-    let remainder = chunks.remainder();
-    
-    let chunk_sum: f32 = chunks
-        .map(|chunk| chunk.iter().sum::<f32>())
-        .sum();
-    
-    let remainder_sum: f32 = remainder.iter().sum();
-    
-    (chunk_sum + remainder_sum) / data.len() as f32
+/// Compare the single-accumulator AVX loop against the 4-accumulator unrolled one
+#[cfg(target_arch = "x86_64")]
+fn print_avx_accumulator_comparison(config: &BenchmarkConfig, rng: &mut StdRng) {
+    if !is_x86_feature_detected!("avx") {
+        return;
+    }
+
+    println!();
+    println!("AVX Accumulator Comparison");
+    println!("{:<12} {:<15} {:<15} {:<12} {:<12}",
+        "Size", "1 Acc (ns)", "4 Acc (ns)", "Speedup", "Accuracy");
+    println!("{}", "-".repeat(67));
+
+    for &size in &config.sizes {
+        let data = black_box(generate_data(size, config.distribution, rng));
+
+        // Warmup runs
+        for _ in 0..config.warmup {
+            let _ = unsafe { calculate_mean_simd_avx_single_acc(&data) };
+            let _ = unsafe { calculate_mean_simd_avx(&data) };
+        }
+
+        let (single_mean, single_time) = time_iterations(config.iters, &data, |data| unsafe {
+            calculate_mean_simd_avx_single_acc(data)
+        });
+        let (multi_mean, multi_time) = time_iterations(config.iters, &data, |data| unsafe {
+            calculate_mean_simd_avx(data)
+        });
+        let single_ns = single_time.min.as_nanos() as f64;
+        let multi_ns = multi_time.min.as_nanos() as f64;
+
+        let speedup = if multi_ns > 0.0 { single_ns / multi_ns } else { 0.0 };
+
+        println!("{:<12} {:<15} {:<15}x {:<12.2}x {:<12.2e}",
+            format_size(size),
+            single_ns as u64,
+            multi_ns as u64,
+            speedup,
+            (single_mean - multi_mean).abs());
+    }
+}
+
+/// Compare the single-accumulator NEON loop against the 4-accumulator unrolled one
+#[cfg(target_arch = "aarch64")]
+fn print_neon_accumulator_comparison(config: &BenchmarkConfig, rng: &mut StdRng) {
+    println!();
+    println!("NEON Accumulator Comparison");
+    println!("{:<12} {:<15} {:<15} {:<12} {:<12}",
+        "Size", "1 Acc (ns)", "4 Acc (ns)", "Speedup", "Accuracy");
+    println!("{}", "-".repeat(67));
+
+    for &size in &config.sizes {
+        let data = black_box(generate_data(size, config.distribution, rng));
+
+        // Warmup runs
+        for _ in 0..config.warmup {
+            let _ = unsafe { calculate_mean_simd_neon_single_acc(&data) };
+            let _ = unsafe { calculate_mean_simd_neon(&data) };
+        }
+
+        let (single_mean, single_time) = time_iterations(config.iters, &data, |data| unsafe {
+            calculate_mean_simd_neon_single_acc(data)
+        });
+        let (multi_mean, multi_time) = time_iterations(config.iters, &data, |data| unsafe {
+            calculate_mean_simd_neon(data)
+        });
+        let single_ns = single_time.min.as_nanos() as f64;
+        let multi_ns = multi_time.min.as_nanos() as f64;
+
+        let speedup = if multi_ns > 0.0 { single_ns / multi_ns } else { 0.0 };
+
+        println!("{:<12} {:<15} {:<15}x {:<12.2}x {:<12.2e}",
+            format_size(size),
+            single_ns as u64,
+            multi_ns as u64,
+            speedup,
+            (single_mean - multi_mean).abs());
+    }
+}
+
+/// Compare aligned and unaligned AVX loads on a 1M-element buffer
+#[cfg(target_arch = "x86_64")]
+fn print_alignment_comparison(config: &BenchmarkConfig, rng: &mut StdRng) {
+    const SIZE: usize = 1_000_000;
+
+    if !is_x86_feature_detected!("avx") {
+        return;
+    }
+
+    // One extra element lets the misaligned view start 4 bytes past the boundary
+    let buffer = AlignedBuffer::from(generate_data(SIZE + 1, config.distribution, rng).as_slice());
+    let aligned = black_box(&buffer[..SIZE]);
+    let misaligned = black_box(&buffer[1..]);
+
+    // Warmup runs
+    for _ in 0..config.warmup {
+        let _ = unsafe { calculate_mean_simd_avx_aligned(aligned) };
+        let _ = unsafe { calculate_mean_simd_avx_unaligned(aligned) };
+        let _ = unsafe { calculate_mean_simd_avx_unaligned(misaligned) };
+    }
+
+    let (_, aligned_time) = time_iterations(config.iters, aligned, |data| unsafe {
+        calculate_mean_simd_avx_aligned(data)
+    });
+    let (_, unaligned_time) = time_iterations(config.iters, aligned, |data| unsafe {
+        calculate_mean_simd_avx_unaligned(data)
+    });
+    let (_, misaligned_time) = time_iterations(config.iters, misaligned, |data| unsafe {
+        calculate_mean_simd_avx_unaligned(data)
+    });
+
+    println!();
+    println!("AVX Load Alignment Comparison ({} elements)", format_size(SIZE));
+    println!("{:<36} {:<15}", "Variant", "Time (ns)");
+    println!("{}", "-".repeat(52));
+    println!("{:<36} {:<15}", "Aligned load, aligned data", aligned_time.min.as_nanos());
+    println!("{:<36} {:<15}", "Unaligned load, aligned data", unaligned_time.min.as_nanos());
+    println!("{:<36} {:<15}", "Unaligned load, misaligned data", misaligned_time.min.as_nanos());
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+struct BenchmarkResults {
+    scalar_mean: f32,
+    simd_mean: f32,
+    chunk_mean: f32,
+    kahan_mean: f32,
+    #[cfg(feature = "portable-simd")]
+    portable_mean: f32,
+    #[cfg(feature = "parallel")]
+    parallel_mean: f32,
+    scalar_time: Timing,
+    simd_time: Timing,
+    chunk_time: Timing,
+    kahan_time: Timing,
+    #[cfg(feature = "portable-simd")]
+    portable_time: Timing,
+    #[cfg(feature = "parallel")]
+    parallel_time: Timing,
+}
+
+impl BenchmarkResults {
+    /// Speedup of `time` relative to scalar, from the minimum, the least noisy estimate
+    fn speedup(&self, time: &Timing) -> f64 {
+        let scalar_ns = self.scalar_time.min.as_nanos() as f64;
+        let ns = time.min.as_nanos() as f64;
+        if ns > 0.0 { scalar_ns / ns } else { 0.0 }
+    }
+
+    /// Maximum difference of the SIMD and chunked means from scalar
+    fn max_diff(&self) -> f32 {
+        let max_diff = (self.scalar_mean - self.simd_mean).abs()
+            .max((self.scalar_mean - self.chunk_mean).abs());
+        #[cfg(feature = "portable-simd")]
+        let max_diff = max_diff.max((self.scalar_mean - self.portable_mean).abs());
+        #[cfg(feature = "parallel")]
+        let max_diff = max_diff.max((self.scalar_mean - self.parallel_mean).abs());
+        max_diff
+    }
+
+    /// Largest coefficient of variation among the implementations' timings
+    fn max_cv(&self) -> f64 {
+        let timings = [
+            &self.scalar_time,
+            &self.simd_time,
+            &self.chunk_time,
+            &self.kahan_time,
+            #[cfg(feature = "portable-simd")]
+            &self.portable_time,
+            #[cfg(feature = "parallel")]
+            &self.parallel_time,
+        ];
+        timings.iter().map(|timing| timing.cv).fold(0.0, f64::max)
+    }
+
+    /// Difference between the compensated (Kahan) and scalar means
+    fn kahan_diff(&self) -> f32 {
+        (self.scalar_mean - self.kahan_mean).abs()
+    }
+}
+
+/// Results for one dataset size
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+struct BenchmarkRun {
+    size: usize,
+    results: BenchmarkResults,
+}
+
+/// Coefficient of variation above which the table warns that timings are unstable
+const CV_WARNING_THRESHOLD: f64 = 0.1;
+
+/// Minimum and median duration over repeated runs of one implementation
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+struct Timing {
+    #[cfg_attr(feature = "serde", serde(with = "duration_nanos"))]
+    min: Duration,
+    #[cfg_attr(feature = "serde", serde(with = "duration_nanos"))]
+    median: Duration,
+    /// Coefficient of variation (standard deviation over mean) of the iterations
+    cv: f64,
+}
+
+/// (De)serialize a `Duration` as a plain nanosecond count, which any JSON reader understands
+#[cfg(feature = "serde")]
+mod duration_nanos {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u128(duration.as_nanos())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let nanos = u128::deserialize(deserializer)?;
+        u64::try_from(nanos).map(Duration::from_nanos).map_err(D::Error::custom)
+    }
+}
+
+/// Run `f` over `data` `iters` times, returning its last result and the timing aggregates
+///
+/// Input and output pass through `black_box` so the optimizer can neither
+/// constant-fold the call nor drop it as unused.
+fn time_iterations(iters: usize, data: &[f32], f: impl Fn(&[f32]) -> f32) -> (f32, Timing) {
+    let mut durations = Vec::with_capacity(iters);
+    let mut result = 0.0;
+
+    for _ in 0..iters {
+        let start = Instant::now();
+        result = black_box(f(black_box(data)));
+        durations.push(start.elapsed());
+    }
+
+    durations.sort();
+    let mid = durations.len() / 2;
+    let median = if durations.len() % 2 == 0 {
+        (durations[mid - 1] + durations[mid]) / 2
+    } else {
+        durations[mid]
+    };
+
+    let ns: Vec<f64> = durations.iter().map(|d| d.as_nanos() as f64).collect();
+    let mean = ns.iter().sum::<f64>() / ns.len() as f64;
+    let variance = ns.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / ns.len() as f64;
+    let cv = if mean > 0.0 { variance.sqrt() / mean } else { 0.0 };
+
+    (result, Timing { min: durations[0], median, cv })
+}
+
+/// Throughput of reading `size` f32s in the minimum time, in GB/s
+fn throughput_gbps(size: usize, timing: &Timing) -> f64 {
+    let bytes = (size * std::mem::size_of::<f32>()) as f64;
+    let ns = timing.min.as_nanos() as f64;
+    // Bytes per nanosecond is exactly GB/s
+    if ns > 0.0 { bytes / ns } else { 0.0 }
+}
+
+fn format_timing(timing: &Timing) -> String {
+    format!("{}/{}", timing.min.as_nanos(), timing.median.as_nanos())
+}
+
+/// Generate random floats drawn from `distribution`
+fn generate_data(size: usize, distribution: Distribution, rng: &mut StdRng) -> Vec<f32> {
+    match distribution {
+        Distribution::Uniform(lo, hi) => (0..size)
+            .map(|_| rng.gen_range(lo..hi))
+            .collect(),
+        Distribution::Normal { mean, stddev } => {
+            let normal = Normal::new(mean, stddev).expect("stddev is validated while parsing");
+            (0..size)
+                .map(|_| rng.sample(normal))
+                .collect()
+        }
+    }
+}
+
+fn format_size(size: usize) -> String {
+    match size {
+        n if n >= 1_000_000 => format!("{}M", n / 1_000_000),
+        n if n >= 1_000 => format!("{}K", n / 1_000),
+        n => n.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiny_config() -> BenchmarkConfig {
+        BenchmarkConfig::new().sizes(vec![8, 16]).warmup(0).iters(1).seed(0)
+    }
+
+    #[test]
+    fn run_yields_one_result_per_size() {
+        let runs: Vec<BenchmarkRun> = tiny_config().run().collect();
+        let sizes: Vec<usize> = runs.iter().map(|run| run.size).collect();
+        assert_eq!(sizes, [8, 16]);
+        for run in &runs {
+            assert!(run.results.max_diff() < 1e-3, "size {}: {}", run.size, run.results.max_diff());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn benchmark_run_round_trips_through_json() {
+        let run = tiny_config().run().next().unwrap();
+        let json = serde_json::to_string(&run).unwrap();
+        let parsed: BenchmarkRun = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, run);
+    }
 }
@@ -0,0 +1,158 @@
+//! Checks of `simd_argmax` and `simd_argmin` against a scalar `iter().enumerate()` scan.
+//!
+//! Also locks down their tie-breaking contract: the lowest index wins, even
+//! when the later copies sit in lower SIMD lanes or the scalar tail.
+
+use proptest::prelude::*;
+
+use simd_poc::{simd_argmax, simd_argmin};
+
+mod common;
+
+use common::data;
+
+/// Index of the first maximum, skipping NaNs
+fn scalar_argmax(data: &[f32]) -> Option<usize> {
+    let mut best: Option<(usize, f32)> = None;
+    for (i, &x) in data.iter().enumerate() {
+        if !x.is_nan() && best.is_none_or(|(_, b)| x > b) {
+            best = Some((i, x));
+        }
+    }
+    best.map(|(i, _)| i)
+}
+
+/// Index of the first minimum, skipping NaNs
+fn scalar_argmin(data: &[f32]) -> Option<usize> {
+    let negated: Vec<f32> = data.iter().map(|x| -x).collect();
+    scalar_argmax(&negated)
+}
+
+proptest! {
+    #[test]
+    fn argmax_matches_scalar(data in data()) {
+        prop_assert_eq!(simd_argmax(&data), scalar_argmax(&data));
+    }
+
+    #[test]
+    fn argmin_matches_scalar(data in data()) {
+        prop_assert_eq!(simd_argmin(&data), scalar_argmin(&data));
+    }
+
+    #[test]
+    fn repeated_values_match_scalar(data in prop::collection::vec(-3i8..3, 0..100)) {
+        // A handful of distinct values, so most extremes are tied
+        let data: Vec<f32> = data.into_iter().map(f32::from).collect();
+        prop_assert_eq!(simd_argmax(&data), scalar_argmax(&data));
+        prop_assert_eq!(simd_argmin(&data), scalar_argmin(&data));
+    }
+}
+
+/// 21 elements: two full AVX chunks and a 5-element scalar tail
+fn ramp_with_extreme_at(position: usize) -> Vec<f32> {
+    let mut data: Vec<f32> = (0..21).map(|i| (i % 7) as f32).collect();
+    data[position] = 100.0;
+    data
+}
+
+#[test]
+fn max_at_start_middle_and_tail() {
+    for position in [0, 10, 19] {
+        assert_eq!(simd_argmax(&ramp_with_extreme_at(position)), Some(position));
+        let negated: Vec<f32> = ramp_with_extreme_at(position).iter().map(|x| -x).collect();
+        assert_eq!(simd_argmin(&negated), Some(position));
+    }
+}
+
+#[test]
+fn ties_resolve_to_the_lowest_index() {
+    let mut data = vec![0.0f32; 21];
+    for position in [13, 6, 19] {
+        data[position] = 5.0;
+    }
+    assert_eq!(simd_argmax(&data), Some(6));
+    assert_eq!(simd_argmin(&data), Some(0));
+}
+
+#[test]
+fn nans_are_ignored() {
+    let mut data = ramp_with_extreme_at(10);
+    data[3] = f32::NAN;
+    data[20] = f32::NAN;
+    assert_eq!(simd_argmax(&data), Some(10));
+    assert_eq!(simd_argmax(&[f32::NAN; 9]), None);
+    assert_eq!(simd_argmin(&[]), None);
+}
+
+/// `len` copies of `fill` with `value` written at each of `positions`
+fn with_ties(len: usize, fill: f32, value: f32, positions: &[usize]) -> Vec<f32> {
+    let mut data = vec![fill; len];
+    for &position in positions {
+        data[position] = value;
+    }
+    data
+}
+
+// Positions chosen so the lowest index sits in a higher SIMD lane than the later
+// copies (5 is lane 5, 8 and 16 are lane 0), plus one in the scalar tail
+const POSITIONS: [usize; 4] = [5, 8, 16, 36];
+
+#[test]
+fn argmax_picks_lowest_of_equal_maxima() {
+    let data = with_ties(37, 1.0, 9.0, &POSITIONS);
+    assert_eq!(simd_argmax(&data), Some(5));
+}
+
+#[test]
+fn argmin_picks_lowest_of_equal_minima() {
+    let data = with_ties(37, 1.0, -9.0, &POSITIONS);
+    assert_eq!(simd_argmin(&data), Some(5));
+}
+
+#[test]
+fn tie_with_the_tail_keeps_the_vector_index() {
+    // The tail copy at 36 must not displace the vector-loop copy at 16
+    let data = with_ties(37, 1.0, 9.0, &[16, 36]);
+    assert_eq!(simd_argmax(&data), Some(16));
+    let data = with_ties(37, 1.0, -9.0, &[16, 36]);
+    assert_eq!(simd_argmin(&data), Some(16));
+}
+
+#[test]
+fn all_equal_picks_first() {
+    for len in [1, 7, 8, 9, 33, 1000] {
+        let data = vec![3.0f32; len];
+        assert_eq!(simd_argmax(&data), Some(0), "len {}", len);
+        assert_eq!(simd_argmin(&data), Some(0), "len {}", len);
+    }
+}
+
+#[test]
+fn signed_zeros_tie() {
+    let mut data = vec![-1.0f32; 20];
+    data[3] = -0.0;
+    data[10] = 0.0;
+    assert_eq!(simd_argmax(&data), Some(3));
+
+    let mut data = vec![1.0f32; 20];
+    data[3] = 0.0;
+    data[10] = -0.0;
+    assert_eq!(simd_argmin(&data), Some(3));
+}
+
+#[test]
+fn ties_at_infinity_pick_first() {
+    let data = with_ties(40, 1.0, f32::INFINITY, &[12, 4, 30]);
+    assert_eq!(simd_argmax(&data), Some(4));
+    let data = with_ties(40, 1.0, f32::NEG_INFINITY, &[12, 4, 30]);
+    assert_eq!(simd_argmin(&data), Some(4));
+}
+
+#[test]
+fn ties_at_the_lane_start_value_pick_first() {
+    // No lane ever moves off its -inf (or +inf) start, so the scalar fallback decides
+    let data = with_ties(40, f32::NAN, f32::NEG_INFINITY, &[12, 4, 30]);
+    assert_eq!(simd_argmax(&data), Some(4));
+    let data = with_ties(40, f32::NAN, f32::INFINITY, &[12, 4, 30]);
+    assert_eq!(simd_argmin(&data), Some(4));
+}
@@ -0,0 +1,60 @@
+//! Checks the `ndarray` axis means against `ArrayBase::mean_axis`.
+
+use ndarray::{Array2, ArrayView2, Axis, ShapeBuilder, s};
+
+use simd_poc::{mean_axis0, mean_axis1};
+
+/// Deterministic values with a wide spread, filled in row-major order
+fn matrix(rows: usize, cols: usize) -> Array2<f32> {
+    Array2::from_shape_fn((rows, cols), |(i, j)| ((i * 37 + j * 101) % 200) as f32 - 100.0)
+}
+
+/// Compare both axis means of `view` against ndarray's own
+fn assert_matches_ndarray(view: ArrayView2<f32>) {
+    for (axis, actual) in [(Axis(0), mean_axis0(&view)), (Axis(1), mean_axis1(&view))] {
+        let expected = view.mean_axis(axis).expect("axis is not empty");
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(&expected) {
+            assert!((a - e).abs() <= 1e-4, "{:?}: {} vs ndarray {}", axis, a, e);
+        }
+    }
+}
+
+#[test]
+fn row_major() {
+    assert_matches_ndarray(matrix(13, 37).view());
+}
+
+#[test]
+fn column_major() {
+    let mut a = Array2::zeros((13, 37).f());
+    a.assign(&matrix(13, 37));
+    assert_matches_ndarray(a.view());
+}
+
+#[test]
+fn transposed() {
+    let a = matrix(21, 9);
+    assert_matches_ndarray(a.t());
+}
+
+#[test]
+fn strided_slice() {
+    let a = matrix(40, 50);
+    assert_matches_ndarray(a.slice(s![1..;3, ..;2]));
+    assert_matches_ndarray(a.slice(s![..;-1, 3..;5]));
+}
+
+#[test]
+fn single_row_and_column() {
+    let a = matrix(1, 17);
+    assert_matches_ndarray(a.view());
+    assert_matches_ndarray(a.t());
+}
+
+#[test]
+fn empty_axis_is_nan() {
+    let a = Array2::<f32>::zeros((3, 0));
+    assert_eq!(mean_axis0(&a.view()).len(), 0);
+    assert!(mean_axis1(&a.view()).iter().all(|m| m.is_nan()));
+}
@@ -0,0 +1,76 @@
+//! Tolerances, reference implementations and data generators shared by the test files.
+
+// Each test file compiles its own copy and uses only part of it
+#![allow(dead_code)]
+
+use proptest::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Finite values, short slices around the SIMD widths plus a few large ones
+pub fn data() -> impl Strategy<Value = Vec<f32>> {
+    let value = -1.0e3f32..1.0e3;
+    prop_oneof![
+        4 => prop::collection::vec(value.clone(), 0..20),
+        1 => prop::collection::vec(value, 1000..5000),
+    ]
+}
+
+/// `len` seeded uniform values in `-1e3..1e3`, for fixed-size tests too large to shrink
+pub fn random_vec<T>(len: usize, seed: u64) -> Vec<T>
+where
+    T: rand::distributions::uniform::SampleUniform + PartialOrd + From<i16>,
+{
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..len).map(|_| rng.gen_range(T::from(-1000)..T::from(1000))).collect()
+}
+
+/// Check `|a - b| <= abs_tol + rel_tol * |b|`, treating two NaNs as equal
+///
+/// The relative part covers rounding of the result itself; the absolute part
+/// covers rounding of intermediate sums, which scales with the magnitude of
+/// what was summed rather than with the (possibly tiny, after cancellation) result.
+/// Returns the failure for `?` in a `proptest!` body; plain tests use [`assert_close`].
+pub fn prop_close(a: f32, b: f32, rel_tol: f32, abs_tol: f32) -> Result<(), TestCaseError> {
+    if a.is_nan() || b.is_nan() {
+        prop_assert!(a.is_nan() && b.is_nan(), "{} vs {}", a, b);
+        return Ok(());
+    }
+    let tolerance = abs_tol + rel_tol * b.abs();
+    prop_assert!((a - b).abs() <= tolerance, "{} vs {} (tolerance {})", a, b, tolerance);
+    Ok(())
+}
+
+/// [`prop_close`], panicking on failure
+#[track_caller]
+pub fn assert_close(a: f32, b: f32, rel_tol: f32, abs_tol: f32) {
+    if let Err(failure) = prop_close(a, b, rel_tol, abs_tol) {
+        panic!("{}", failure);
+    }
+}
+
+/// Mean absolute value of `data`, but at least 1.0
+pub fn magnitude(data: &[f32]) -> f32 {
+    if data.is_empty() {
+        return 1.0;
+    }
+    (data.iter().map(|x| x.abs()).sum::<f32>() / data.len() as f32).max(1.0)
+}
+
+/// Absolute tolerance between two means of `data` that both accumulate in f32
+///
+/// Every f32 addition rounds relative to the running sum, so the further an
+/// accumulator gets into the slice the more error it picks up: the scalar loop
+/// and each lane of the AVX accumulators drift from the exact sum by roughly
+/// `sqrt(len)` roundings of the data's magnitude, and in different directions
+/// because they add in different orders. On large inputs this is far looser
+/// than what the f64-accumulator and Kahan means need, whose error stays
+/// within a rounding or two of the result however long the slice is.
+pub fn f32_accumulator_tol(data: &[f32]) -> f32 {
+    1e-6 * magnitude(data) * (data.len() as f32).sqrt()
+}
+
+/// Mean of `data` summed in f64, which is exact for these lengths and ranges up to f64 rounding
+pub fn reference_mean(data: &[f32]) -> f32 {
+    (data.iter().map(|&x| x as f64).sum::<f64>() / data.len() as f64) as f32
+}
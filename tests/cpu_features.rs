@@ -0,0 +1,39 @@
+//! Checks the feature list `report_cpu_features` prints for bug reports.
+
+use simd_poc::report_cpu_features;
+
+fn names() -> Vec<&'static str> {
+    report_cpu_features().into_iter().map(|(name, _)| name).collect()
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn reports_x86_features() {
+    assert_eq!(names(), ["sse2", "avx", "avx2", "fma", "avx512f"]);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn sse2_is_baseline() {
+    // Every x86_64 CPU has SSE2, so only force-scalar can hide it
+    let sse2 = report_cpu_features().into_iter().any(|(name, available)| name == "sse2" && available);
+    assert_eq!(sse2, !cfg!(feature = "force-scalar"));
+}
+
+#[cfg(target_arch = "aarch64")]
+#[test]
+fn reports_neon() {
+    assert_eq!(names(), ["neon"]);
+}
+
+#[cfg(target_arch = "wasm32")]
+#[test]
+fn reports_simd128() {
+    assert_eq!(names(), ["simd128"]);
+}
+
+#[cfg(target_arch = "riscv64")]
+#[test]
+fn reports_vector_extension() {
+    assert_eq!(names(), ["v"]);
+}
@@ -0,0 +1,182 @@
+//! Checks of the in-place, element-wise and scan SIMD transforms against scalar loops.
+//!
+//! Each element-wise output is a single rounded operation, so those must match
+//! the scalar result exactly. Scans add in a different order than the scalar
+//! loop, so they are checked exactly on small integers and within a tolerance
+//! otherwise.
+
+use simd_poc::{
+    InvalidAlpha, LengthMismatch, ZeroWindow, ema, moving_average, prefix_sum, simd_add,
+    simd_add_vec, simd_normalize_inplace, simd_scale_inplace,
+};
+
+/// 0.5, 1.5, ... up to `len` elements, with alternating signs
+fn signed_ramp(len: usize) -> Vec<f32> {
+    (0..len).map(|i| if i % 2 == 0 { i as f32 + 0.5 } else { -(i as f32) - 0.5 }).collect()
+}
+
+#[test]
+fn scale_multiplies_every_element() {
+    // 13 elements: one AVX chunk and a 5-element scalar tail
+    let mut data = signed_ramp(13);
+    let expected: Vec<f32> = data.iter().map(|x| x * 1.7).collect();
+    simd_scale_inplace(&mut data, 1.7);
+    assert_eq!(data, expected);
+
+    let mut empty: [f32; 0] = [];
+    simd_scale_inplace(&mut empty, 2.0);
+}
+
+#[test]
+fn normalize_divides_by_the_max() {
+    let mut data = signed_ramp(13);
+    data[12] = 40.0;
+    let expected: Vec<f32> = data.iter().map(|x| x / 40.0).collect();
+    simd_normalize_inplace(&mut data);
+    assert_eq!(data, expected);
+    assert_eq!(data[12], 1.0);
+}
+
+#[test]
+fn normalize_leaves_degenerate_slices_untouched() {
+    let mut empty: [f32; 0] = [];
+    simd_normalize_inplace(&mut empty);
+
+    let mut zeros = [0.0f32, -1.0, 0.0, -3.0, 0.0, 0.0, 0.0, 0.0, -2.0];
+    let before = zeros;
+    simd_normalize_inplace(&mut zeros);
+    assert_eq!(zeros, before);
+
+    let mut nans = [f32::NAN; 9];
+    simd_normalize_inplace(&mut nans);
+    assert!(nans.iter().all(|x| x.is_nan()));
+}
+
+#[test]
+fn add_of_length_13() {
+    let a = signed_ramp(13);
+    let b: Vec<f32> = (0..13).map(|i| i as f32 * 0.25).collect();
+    let expected: Vec<f32> = a.iter().zip(&b).map(|(x, y)| x + y).collect();
+
+    let mut out = vec![0.0f32; 13];
+    simd_add(&a, &b, &mut out).unwrap();
+    assert_eq!(out, expected);
+    assert_eq!(simd_add_vec(&a, &b), Ok(expected));
+}
+
+#[test]
+fn add_rejects_mismatched_lengths() {
+    let mut out = vec![0.0f32; 13];
+    assert_eq!(simd_add(&[1.0; 13], &[1.0; 12], &mut out), Err(LengthMismatch { left: 13, right: 12 }));
+    assert!(simd_add(&[1.0; 13], &[1.0; 13], &mut out[..12]).is_err());
+    assert_eq!(simd_add_vec(&[1.0; 3], &[]), Err(LengthMismatch { left: 3, right: 0 }));
+}
+
+/// Scalar running sum, accumulated in f64
+fn running_sum(data: &[f32]) -> Vec<f64> {
+    data.iter()
+        .scan(0.0f64, |sum, &x| {
+            *sum += x as f64;
+            Some(*sum)
+        })
+        .collect()
+}
+
+#[test]
+fn prefix_sum_across_block_boundaries() {
+    for len in [0, 1, 7, 8, 9, 17, 64, 1000] {
+        // Small integers keep every partial sum exact, whatever the order of additions
+        let data: Vec<f32> = (0..len).map(|i| (i % 13) as f32 - 6.0).collect();
+        let expected: Vec<f32> = running_sum(&data).into_iter().map(|x| x as f32).collect();
+        assert_eq!(prefix_sum(&data), expected, "len {}", len);
+    }
+}
+
+#[test]
+fn prefix_sum_matches_scalar_running_sum() {
+    let data = signed_ramp(1000).iter().map(|x| x.sin()).collect::<Vec<f32>>();
+    let magnitude: f64 = data.iter().map(|&x| x.abs() as f64).sum();
+    for (i, (&actual, expected)) in prefix_sum(&data).iter().zip(running_sum(&data)).enumerate() {
+        let tolerance = 4.0 * f32::EPSILON as f64 * magnitude;
+        assert!((actual as f64 - expected).abs() <= tolerance, "index {}: {} vs {}", i, actual, expected);
+    }
+}
+
+/// Brute-force mean of the window starting at `start`, summed in f64
+fn window_mean(data: &[f32], start: usize, window: usize) -> f32 {
+    (data[start..start + window].iter().map(|&x| x as f64).sum::<f64>() / window as f64) as f32
+}
+
+/// Check the windows starting at each of `starts` against a brute-force mean
+fn assert_windows_match(data: &[f32], window: usize, starts: impl Iterator<Item = usize>) {
+    let averages = moving_average(data, window).unwrap();
+    assert_eq!(averages.len(), data.len() - window + 1);
+    for start in starts {
+        let expected = window_mean(data, start, window);
+        let tolerance = 4.0 * f32::EPSILON * expected.abs().max(1.0);
+        assert!(
+            (averages[start] - expected).abs() <= tolerance,
+            "window {} at {}: {} vs brute force {}",
+            window,
+            start,
+            averages[start],
+            expected
+        );
+    }
+}
+
+#[test]
+fn moving_average_matches_brute_force() {
+    let data = signed_ramp(100).iter().map(|x| x.cos() * 10.0).collect::<Vec<f32>>();
+    for window in [1, 2, 7, 8, 9, 33, 100] {
+        assert_windows_match(&data, window, 0..=data.len() - window);
+    }
+}
+
+#[test]
+fn moving_average_of_a_long_positive_input_does_not_cancel() {
+    // All-positive values, as with benchmark data: an f32 prefix sum reaches
+    // ~6e7 here, where its rounding swamps a 50-element window's sum
+    let data: Vec<f32> = (0..1_000_000u32).map(|i| 20.0 + (i.wrapping_mul(2654435761) % 8000) as f32 / 100.0).collect();
+    let window = 50;
+    let last = data.len() - window;
+    let starts = (0..last).step_by(997).chain(last - 100..=last);
+    assert_windows_match(&data, window, starts);
+}
+
+#[test]
+fn moving_average_edge_cases() {
+    assert_eq!(moving_average(&[1.0, 2.0], 0), Err(ZeroWindow));
+    assert_eq!(moving_average(&[1.0, 2.0], 3), Ok(vec![]));
+    assert_eq!(moving_average(&[1.0, 2.0, 6.0], 3), Ok(vec![3.0]));
+    assert_eq!(moving_average(&[], 1), Ok(vec![]));
+}
+
+/// The EMA recurrence written out over indices, seeded with the first value
+fn naive_ema(data: &[f32], alpha: f32) -> Vec<f32> {
+    let mut out = vec![0.0; data.len()];
+    for i in 0..data.len() {
+        let prev = if i == 0 { data[0] } else { out[i - 1] };
+        out[i] = alpha * data[i] + (1.0 - alpha) * prev;
+    }
+    out
+}
+
+#[test]
+fn ema_matches_naive_recurrence() {
+    let data = signed_ramp(1000).iter().map(|x| x.sin() * 10.0).collect::<Vec<f32>>();
+    for alpha in [0.01, 0.1, 0.5, 0.9, 1.0] {
+        assert_eq!(ema(&data, alpha).unwrap(), naive_ema(&data, alpha), "alpha {}", alpha);
+    }
+    // Alpha of one tracks the input exactly
+    assert_eq!(ema(&data, 1.0).unwrap(), data);
+    assert_eq!(ema(&[], 0.5), Ok(vec![]));
+}
+
+#[test]
+fn ema_rejects_alpha_outside_zero_to_one() {
+    for alpha in [0.0, -0.5, 1.000_001, f32::INFINITY] {
+        assert_eq!(ema(&[1.0], alpha), Err(InvalidAlpha(alpha)));
+    }
+    assert!(ema(&[1.0], f32::NAN).is_err());
+}
@@ -0,0 +1,68 @@
+//! Checks `mean_of_file` against values written to a temporary file.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use simd_poc::mean_of_file;
+
+/// A path in the temp directory unique to this process and test
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("simd_poc-{}-{}.f32", std::process::id(), name))
+}
+
+/// Write `bytes` to a fresh temp file, run `f` on its path, then remove it
+fn with_file<T>(name: &str, bytes: &[u8], f: impl FnOnce(&PathBuf) -> T) -> T {
+    let path = temp_path(name);
+    fs::write(&path, bytes).unwrap();
+    let result = f(&path);
+    fs::remove_file(&path).unwrap();
+    result
+}
+
+fn to_bytes(values: &[f32]) -> Vec<u8> {
+    values.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+#[test]
+fn known_values() {
+    let values = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+    let mean = with_file("known", &to_bytes(&values), |path| mean_of_file(path).unwrap());
+    assert_eq!(mean, Some(5.5));
+}
+
+#[test]
+fn spans_many_chunks() {
+    // 1M values is 4 MB, far more than one read buffer
+    let values: Vec<f32> = (0..1_000_000).map(|i| (i % 100) as f32).collect();
+    let mean = with_file("large", &to_bytes(&values), |path| mean_of_file(path).unwrap());
+    assert_eq!(mean, Some(49.5));
+}
+
+#[test]
+fn length_on_a_chunk_boundary() {
+    // Two full 64 KiB reads, then one that hits end of file with nothing in it
+    let values: Vec<f32> = (0..32_768).map(|i| (i % 2) as f32).collect();
+    let mean = with_file("boundary", &to_bytes(&values), |path| mean_of_file(path).unwrap());
+    assert_eq!(mean, Some(0.5));
+}
+
+#[test]
+fn empty_file_is_none() {
+    let mean = with_file("empty", &[], |path| mean_of_file(path).unwrap());
+    assert_eq!(mean, None);
+}
+
+#[test]
+fn partial_value_is_an_error() {
+    let mut bytes = to_bytes(&[1.0, 2.0]);
+    bytes.push(0);
+    let err = with_file("partial", &bytes, |path| mean_of_file(path).unwrap_err());
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn missing_file_is_an_error() {
+    let err = mean_of_file(&temp_path("missing")).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
@@ -0,0 +1,52 @@
+//! Checks that the `force-scalar` build routes the SIMD entry points to the scalar code.
+//!
+//! Run with `cargo test --features force-scalar`.
+
+use simd_poc::{
+    best_mean_impl, calculate_mean_i32, calculate_mean_scalar, calculate_mean_u8, calculate_mean_simd, detect_backend, report_cpu_features,
+    simd_max, simd_min, simd_min_max,
+};
+
+fn data(len: usize) -> Vec<f32> {
+    (0..len).map(|i| ((i * 7919) % 1000) as f32 * 0.37 - 150.0).collect()
+}
+
+#[test]
+fn mean_is_bit_identical_to_scalar() {
+    for len in [0, 1, 7, 8, 9, 31, 32, 33, 1000, 4099] {
+        let data = data(len);
+        let scalar = calculate_mean_scalar(&data);
+        assert_eq!(calculate_mean_simd(&data).to_bits(), scalar.to_bits(), "len {}", len);
+        assert_eq!(best_mean_impl()(&data).to_bits(), scalar.to_bits(), "len {}", len);
+    }
+}
+
+#[test]
+fn backend_is_scalar() {
+    let backend = detect_backend();
+    assert_eq!(backend.width(), 1);
+    let data = data(1000);
+    assert_eq!(backend.sum(&data), data.iter().sum::<f32>());
+}
+
+#[test]
+fn min_max_agree() {
+    let data = data(1000);
+    assert_eq!(simd_min_max(&data), simd_min(&data).zip(simd_max(&data)));
+}
+
+#[test]
+fn integer_means_are_exact() {
+    let ints: Vec<i32> = (0..1001).map(|i| if i % 2 == 0 { i32::MAX - i } else { i32::MIN + i }).collect();
+    let sum: i64 = ints.iter().map(|&x| x as i64).sum();
+    assert_eq!(calculate_mean_i32(&ints), sum as f64 / ints.len() as f64);
+
+    let bytes: Vec<u8> = (0..1001).map(|i| (i * 37 % 256) as u8).collect();
+    let sum: u64 = bytes.iter().map(|&x| x as u64).sum();
+    assert_eq!(calculate_mean_u8(&bytes), sum as f64 / bytes.len() as f64);
+}
+
+#[test]
+fn every_feature_is_reported_missing() {
+    assert!(report_cpu_features().iter().all(|&(_, available)| !available));
+}
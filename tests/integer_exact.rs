@@ -0,0 +1,101 @@
+//! Bit-exact checks of the integer means against scalar references.
+//!
+//! Integer lanes are summed without rounding, so unlike the float means these
+//! must match the reference exactly. A mismatch is narrowed down to the first
+//! element whose inclusion makes the results diverge.
+
+use std::fmt::Debug;
+
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+use simd_poc::{calculate_mean_i32, calculate_mean_u8};
+
+fn reference_i32(data: &[i32]) -> f64 {
+    let sum: i64 = data.iter().map(|&x| x as i64).sum();
+    sum as f64 / data.len() as f64
+}
+
+fn reference_u8(data: &[u8]) -> f64 {
+    let sum: u64 = data.iter().map(|&x| x as u64).sum();
+    sum as f64 / data.len() as f64
+}
+
+/// Assert `mean(data)` and `reference(data)` have identical bits, reporting the diverging element if not
+fn assert_bit_exact<T: Copy + Debug>(data: &[T], mean: fn(&[T]) -> f64, reference: fn(&[T]) -> f64) {
+    let (actual, expected) = (mean(data), reference(data));
+    if actual.to_bits() == expected.to_bits() {
+        return;
+    }
+
+    // The shortest diverging prefix ends at the element that broke the accumulation
+    let index = (0..=data.len())
+        .find(|&end| mean(&data[..end]).to_bits() != reference(&data[..end]).to_bits())
+        .expect("the whole slice diverges")
+        .saturating_sub(1);
+    panic!(
+        "len {}: {} vs reference {}; first diverges at index {} (value {:?})",
+        data.len(),
+        actual,
+        expected,
+        index,
+        data.get(index)
+    );
+}
+
+/// Every length up to a few AVX2 blocks, so each remainder is covered
+const LENGTHS: std::ops::RangeInclusive<usize> = 0..=300;
+
+#[test]
+fn i32_random_is_exact() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for len in LENGTHS {
+        let data: Vec<i32> = (0..len).map(|_| rng.r#gen()).collect();
+        assert_bit_exact(&data, calculate_mean_i32, reference_i32);
+    }
+}
+
+#[test]
+fn i32_extremes_are_exact() {
+    // All-MAX and all-MIN overflow any i32 accumulator; alternating them checks sign extension
+    for len in LENGTHS {
+        assert_bit_exact(&vec![i32::MAX; len], calculate_mean_i32, reference_i32);
+        assert_bit_exact(&vec![i32::MIN; len], calculate_mean_i32, reference_i32);
+        let alternating: Vec<i32> = (0..len).map(|i| if i % 2 == 0 { i32::MIN } else { i32::MAX }).collect();
+        assert_bit_exact(&alternating, calculate_mean_i32, reference_i32);
+    }
+}
+
+#[test]
+fn i32_near_max_does_not_overflow() {
+    // 10000 values within 100 of i32::MAX sum to ~2^44, far past any i32 accumulator
+    let data: Vec<i32> = (0..10_000).map(|i| i32::MAX - i % 100).collect();
+    assert_bit_exact(&data, calculate_mean_i32, reference_i32);
+    assert!(calculate_mean_i32(&data) > (i32::MAX - 100) as f64);
+}
+
+#[test]
+fn u8_random_is_exact() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for len in LENGTHS {
+        let data: Vec<u8> = (0..len).map(|_| rng.r#gen()).collect();
+        assert_bit_exact(&data, calculate_mean_u8, reference_u8);
+    }
+}
+
+#[test]
+fn u8_random_megabyte_is_exact() {
+    // 2^20 + 7 bytes, so the 32-byte blocks leave a scalar tail
+    let mut rng = StdRng::seed_from_u64(1);
+    let data: Vec<u8> = (0..(1 << 20) + 7).map(|_| rng.r#gen()).collect();
+    assert_bit_exact(&data, calculate_mean_u8, reference_u8);
+}
+
+#[test]
+fn u8_extremes_are_exact() {
+    // 255 everywhere overflows any narrow lane accumulator first
+    for len in LENGTHS {
+        assert_bit_exact(&vec![u8::MAX; len], calculate_mean_u8, reference_u8);
+    }
+    assert_bit_exact(&vec![u8::MAX; 1 << 20], calculate_mean_u8, reference_u8);
+}
@@ -0,0 +1,49 @@
+//! Checks `mean_of_mmap` against the buffered `mean_of_file` on the same files.
+//!
+//! Run with `cargo test --features mmap`.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use simd_poc::{mean_of_file, mean_of_mmap};
+
+/// Write `bytes` to a fresh temp file, run `f` on its path, then remove it
+fn with_file<T>(name: &str, bytes: &[u8], f: impl FnOnce(&PathBuf) -> T) -> T {
+    let path = std::env::temp_dir().join(format!("simd_poc-mmap-{}-{}.f32", std::process::id(), name));
+    fs::write(&path, bytes).unwrap();
+    let result = f(&path);
+    fs::remove_file(&path).unwrap();
+    result
+}
+
+fn to_bytes(values: &[f32]) -> Vec<u8> {
+    values.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+#[test]
+fn matches_buffered_reader() {
+    for len in [1, 7, 8, 9, 1000, 100_003] {
+        let values: Vec<f32> = (0..len).map(|i| ((i * 7919) % 1000) as f32 * 0.37 - 150.0).collect();
+        let (mapped, buffered) = with_file(&format!("len{}", len), &to_bytes(&values), |path| {
+            (mean_of_mmap(path).unwrap().unwrap(), mean_of_file(path).unwrap().unwrap())
+        });
+        // The buffered version merges f64 partials, so the two round differently
+        let scale = values.iter().map(|x| x.abs()).sum::<f32>() / len as f32;
+        let tolerance = 1e-6 * scale * (len as f32).sqrt();
+        assert!((mapped - buffered).abs() <= tolerance, "len {}: {} vs {}", len, mapped, buffered);
+    }
+}
+
+#[test]
+fn empty_file_is_none() {
+    assert_eq!(with_file("empty", &[], |path| mean_of_mmap(path).unwrap()), None);
+}
+
+#[test]
+fn partial_value_is_an_error() {
+    let mut bytes = to_bytes(&[1.0, 2.0]);
+    bytes.extend([0, 0]);
+    let err = with_file("partial", &bytes, |path| mean_of_mmap(path).unwrap_err());
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
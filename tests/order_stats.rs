@@ -0,0 +1,102 @@
+//! Checks of the quickselect-based order statistics against a full sort.
+
+use simd_poc::{mad, median, percentile, top_k};
+
+mod common;
+
+use common::assert_close;
+
+/// The median of a sorted copy of `data`
+fn sorted_median(data: &[f32]) -> f32 {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 1 { sorted[mid] } else { sorted[mid - 1] * 0.5 + sorted[mid] * 0.5 }
+}
+
+/// A shuffled-looking sequence of distinct values
+fn scrambled(len: usize) -> Vec<f32> {
+    (0..len as u32).map(|i| (i.wrapping_mul(2654435761) % 10007) as f32 - 5000.0).collect()
+}
+
+#[test]
+fn median_of_odd_lengths_is_the_middle_element() {
+    assert_eq!(median(&mut [3.0, 1.0, 2.0]), Some(2.0));
+    for len in [1, 7, 101, 10_001] {
+        let data = scrambled(len);
+        assert_eq!(median(&mut data.clone()), Some(sorted_median(&data)), "len {}", len);
+    }
+}
+
+#[test]
+fn median_of_even_lengths_averages_the_middle_pair() {
+    assert_eq!(median(&mut [4.0, 1.0, 3.0, 2.0]), Some(2.5));
+    for len in [2, 8, 100, 10_000] {
+        let data = scrambled(len);
+        assert_eq!(median(&mut data.clone()), Some(sorted_median(&data)), "len {}", len);
+    }
+}
+
+#[test]
+fn median_of_equal_values() {
+    assert_eq!(median(&mut [7.5; 9]), Some(7.5));
+    assert_eq!(median(&mut [7.5; 10]), Some(7.5));
+    assert_eq!(median(&mut []), None);
+}
+
+#[test]
+fn median_of_extreme_middle_pairs() {
+    // Averaging through hi - lo would give inf - inf = NaN and overflow to inf
+    assert_eq!(median(&mut [f32::INFINITY, f32::INFINITY]), Some(f32::INFINITY));
+    assert_eq!(median(&mut [f32::MAX, -f32::MAX]), Some(0.0));
+    assert_eq!(median(&mut [f32::MAX, 1.0, -f32::MAX, f32::MAX]), Some(f32::MAX * 0.5 + 0.5));
+}
+
+#[test]
+fn a_single_nan_makes_the_median_nan() {
+    let mut data = scrambled(11);
+    data[4] = f32::NAN;
+    assert!(median(&mut data).unwrap().is_nan());
+    assert!(median(&mut [f32::NAN]).unwrap().is_nan());
+}
+
+#[test]
+fn top_k_ranks_nans_by_sign() {
+    let negative_nan = -f32::NAN;
+    assert!(negative_nan.is_sign_negative());
+    let data = [1.0, negative_nan, f32::NAN, f32::INFINITY, f32::NEG_INFINITY, 2.0];
+
+    // A positive NaN outranks +inf, while a negative NaN sits below -inf
+    let bits = |values: Vec<f32>| values.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+    let expected = [f32::NAN, f32::INFINITY, 2.0, 1.0, f32::NEG_INFINITY, negative_nan];
+    assert_eq!(bits(top_k(&data, 6)), bits(expected.to_vec()));
+    assert_eq!(bits(top_k(&data, 2)), bits(vec![f32::NAN, f32::INFINITY]));
+    assert_eq!(top_k(&[negative_nan, 3.0, 5.0], 2), [5.0, 3.0]);
+}
+
+#[test]
+fn mad_of_a_known_dataset() {
+    // Median 2, absolute deviations [1, 1, 0, 0, 2, 4, 7] with median 1
+    assert_eq!(mad(&mut [1.0, 1.0, 2.0, 2.0, 4.0, 6.0, 9.0]), Some(1.0));
+    for len in [1, 8, 1001, 10_000] {
+        let data = scrambled(len);
+        let center = sorted_median(&data);
+        let deviations: Vec<f32> = data.iter().map(|x| (x - center).abs()).collect();
+        assert_eq!(mad(&mut data.clone()), Some(sorted_median(&deviations)), "len {}", len);
+    }
+    assert_eq!(mad(&mut [5.0; 12]), Some(0.0));
+    assert_eq!(mad(&mut []), None);
+}
+
+#[test]
+fn percentile_between_extreme_neighbours() {
+    // Equal infinite neighbours must not interpolate through inf - inf
+    assert_eq!(percentile(&mut [f32::INFINITY, 1.0, f32::INFINITY], 0.75), Some(f32::INFINITY));
+    assert_eq!(percentile(&mut [f32::NEG_INFINITY, f32::NEG_INFINITY, 1.0], 0.25), Some(f32::NEG_INFINITY));
+
+    // hi - lo would overflow to +inf for neighbours of opposite sign
+    assert_eq!(percentile(&mut [f32::MAX, -f32::MAX], 0.5), Some(0.0));
+    let quarter = percentile(&mut [f32::MAX, -f32::MAX], 0.25).unwrap();
+    assert_close(quarter, -f32::MAX / 2.0, f32::EPSILON, 0.0);
+    assert_eq!(percentile(&mut [f32::MAX, f32::MAX, 0.0], 0.9), Some(f32::MAX));
+}
@@ -0,0 +1,596 @@
+//! Property tests checking the SIMD and chunked means against the scalar reference.
+
+use proptest::prelude::*;
+
+use simd_poc::{
+    IndexOutOfBounds, LengthMismatch, Partial, ScalarBackend, SimdReduce, best_mean_impl,
+    calculate_mean_chunks, calculate_mean_i32, calculate_mean_kahan, calculate_mean_pairwise,
+    calculate_mean_scalar, calculate_mean_scalar_wide, calculate_mean_simd, calculate_mean_simd_f64,
+    calculate_mean_simd_wide, calculate_mean_skipnan, calculate_mean_u8,
+    calculate_sum_and_sumsq_avx, clamped_mean, detect_backend, mean_chunks, mean_indexed, mean_iter,
+    mean_scalar, mean_simd, mean_strided, mean_where_gt, nmse, simd_count_gt, simd_dot,
+    simd_f32_to_f64_vec, simd_f64_to_f32_vec, simd_l1_norm, simd_max, simd_min, simd_min_max,
+    simd_sum, simd_sum_abs, simd_sum_count, simd_sum_f64acc, simd_sum_squares,
+};
+
+#[cfg(target_arch = "x86_64")]
+use simd_poc::{
+    AvxBackend, Sse2Backend, calculate_mean_simd_avx512, calculate_mean_simd_avx_single_acc,
+    calculate_mean_simd_sse,
+};
+
+#[cfg(target_arch = "aarch64")]
+use simd_poc::{calculate_mean_simd_neon, calculate_mean_simd_neon_single_acc};
+
+#[cfg(all(target_arch = "riscv64", target_feature = "v"))]
+use simd_poc::calculate_mean_simd_rvv;
+
+#[cfg(feature = "parallel")]
+use simd_poc::calculate_mean_parallel;
+
+#[cfg(debug_assertions)]
+use simd_poc::checked_mean;
+
+mod common;
+
+use common::{
+    assert_close, data, f32_accumulator_tol, magnitude, prop_close, random_vec, reference_mean,
+};
+
+/// Every backend this CPU can run, forced individually
+fn backends() -> Vec<Box<dyn SimdReduce>> {
+    let backends: [Option<Box<dyn SimdReduce>>; _] = [
+        Some(Box::new(ScalarBackend)),
+        Some(detect_backend()),
+        #[cfg(target_arch = "x86_64")]
+        AvxBackend::new().map(|backend| Box::new(backend) as _),
+        #[cfg(target_arch = "x86_64")]
+        Sse2Backend::new().map(|backend| Box::new(backend) as _),
+    ];
+    backends.into_iter().flatten().collect()
+}
+
+proptest! {
+    #[test]
+    fn simd_matches_scalar(data in data()) {
+        prop_close(calculate_mean_simd(&data), calculate_mean_scalar(&data), 0.0, f32_accumulator_tol(&data))?;
+    }
+
+    #[test]
+    fn best_impl_matches_scalar(data in data()) {
+        let mean = best_mean_impl();
+        prop_close(mean(&data), calculate_mean_scalar(&data), 0.0, f32_accumulator_tol(&data))?;
+    }
+
+    #[test]
+    fn chunks_match_scalar(data in data()) {
+        prop_close(calculate_mean_chunks(&data), calculate_mean_scalar(&data), 0.0, f32_accumulator_tol(&data))?;
+    }
+
+    #[test]
+    fn backends_match_scalar(data in data()) {
+        for backend in backends() {
+            let mean = backend.sum(&data) / data.len() as f32;
+            prop_close(mean, calculate_mean_scalar(&data), 0.0, f32_accumulator_tol(&data))?;
+        }
+    }
+
+    #[test]
+    fn tails_match_scalar(data in prop::collection::vec(-1.0e3f32..1.0e3, 8..=23)) {
+        // Every remainder mod 8, including the 4-7 float tails taken by the SSE step
+        prop_close(calculate_mean_simd(&data), calculate_mean_scalar(&data), 0.0, f32_accumulator_tol(&data))?;
+        for backend in backends() {
+            let mean = backend.sum(&data) / data.len() as f32;
+            prop_close(mean, calculate_mean_scalar(&data), 0.0, f32_accumulator_tol(&data))?;
+        }
+    }
+
+    #[test]
+    fn strided_matches_scalar(data in data(), stride in 1usize..10, offset in 0usize..10) {
+        let picked: Vec<f32> = data.iter().copied().skip(offset).step_by(stride).collect();
+        match mean_strided(&data, stride, offset) {
+            Some(mean) => prop_close(mean, calculate_mean_scalar(&picked), 0.0, f32_accumulator_tol(&picked))?,
+            None => prop_assert!(offset >= data.len()),
+        }
+    }
+
+    #[test]
+    fn min_max_match_scalar(data in data(), nans in prop::collection::vec(any::<prop::sample::Index>(), 0..3)) {
+        let mut data = data;
+        let len = data.len();
+        for nan in nans.iter().filter(|_| len > 0) {
+            data[nan.index(len)] = f32::NAN;
+        }
+        let scalar_min = data.iter().copied().reduce(f32::min);
+        let scalar_max = data.iter().copied().reduce(f32::max);
+        prop_assert_eq!(simd_min(&data).map(f32::to_bits), scalar_min.map(f32::to_bits));
+        prop_assert_eq!(simd_max(&data).map(f32::to_bits), scalar_max.map(f32::to_bits));
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn avx_single_acc_matches_scalar(data in data()) {
+        prop_assume!(is_x86_feature_detected!("avx"));
+        let mean = unsafe { calculate_mean_simd_avx_single_acc(&data) };
+        prop_close(mean, calculate_mean_scalar(&data), 0.0, f32_accumulator_tol(&data))?;
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    #[test]
+    fn neon_matches_scalar(data in data()) {
+        let mean = unsafe { calculate_mean_simd_neon(&data) };
+        prop_close(mean, calculate_mean_scalar(&data), 0.0, f32_accumulator_tol(&data))?;
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    #[test]
+    fn neon_single_acc_matches_scalar(data in data()) {
+        let mean = unsafe { calculate_mean_simd_neon_single_acc(&data) };
+        prop_close(mean, calculate_mean_scalar(&data), 0.0, f32_accumulator_tol(&data))?;
+    }
+
+    #[test]
+    fn sequential_indices_match_contiguous(data in data()) {
+        let indices: Vec<u32> = (0..data.len() as u32).collect();
+        match mean_indexed(&data, &indices) {
+            Ok(Some(mean)) => prop_close(mean, calculate_mean_scalar(&data), 0.0, f32_accumulator_tol(&data))?,
+            result => prop_assert!(data.is_empty() && result == Ok(None), "{:?}", result),
+        }
+    }
+
+    #[test]
+    fn indexed_matches_scalar(data in data(), picks in prop::collection::vec(any::<prop::sample::Index>(), 1..50)) {
+        prop_assume!(!data.is_empty());
+        let indices: Vec<u32> = picks.iter().map(|pick| pick.index(data.len()) as u32).collect();
+        let picked: Vec<f32> = indices.iter().map(|&i| data[i as usize]).collect();
+        let mean = mean_indexed(&data, &indices).unwrap().unwrap();
+        prop_close(mean, calculate_mean_scalar(&picked), 0.0, f32_accumulator_tol(&picked))?;
+    }
+
+    #[test]
+    fn nmse_of_identical_signals_is_zero(data in data()) {
+        prop_assert_eq!(nmse(&data, &data), Ok(0.0));
+    }
+
+    #[test]
+    fn nmse_matches_f64_reference(data in data(), noise in prop::collection::vec(-1.0f32..1.0, 5000)) {
+        prop_assume!(data.iter().any(|&x| x != 0.0));
+        let estimate: Vec<f32> = data.iter().zip(&noise).map(|(&x, &n)| x + n).collect();
+        let error: f64 = data.iter().zip(&estimate).map(|(&r, &e)| ((r - e) as f64).powi(2)).sum();
+        let energy: f64 = data.iter().map(|&r| (r as f64).powi(2)).sum();
+        let expected = (error / energy) as f32;
+        // Both sums add only non-negative terms, so their f32 error is relative to the result
+        prop_close(nmse(&data, &estimate).unwrap(), expected, 1e-4, 0.0)?;
+    }
+
+    #[test]
+    fn min_max_matches_separate(data in data()) {
+        let separate = simd_min(&data).zip(simd_max(&data));
+        prop_assert_eq!(simd_min_max(&data), separate);
+    }
+
+    #[cfg(all(target_arch = "riscv64", target_feature = "v"))]
+    #[test]
+    fn rvv_matches_scalar(data in data()) {
+        prop_close(calculate_mean_simd_rvv(&data), calculate_mean_scalar(&data), 0.0, f32_accumulator_tol(&data))?;
+    }
+
+    #[test]
+    fn wide_mean_matches_f64_reference(data in data()) {
+        // Summed in f64, so only the final conversion to f32 rounds noticeably
+        prop_close(calculate_mean_simd_wide(&data), reference_mean(&data), f32::EPSILON, 1e-12 * magnitude(&data))?;
+    }
+
+    #[test]
+    fn kahan_mean_matches_f64_reference(data in data()) {
+        // Compensation keeps the sum within a couple of roundings of the magnitude, at any length
+        let abs_tol = 2.0 * f32::EPSILON * magnitude(&data);
+        prop_close(calculate_mean_kahan(&data), reference_mean(&data), f32::EPSILON, abs_tol)?;
+    }
+
+    #[test]
+    fn f64acc_sum_matches_f64_reference(data in data()) {
+        // Every f32 is exact in f64, so only the f64 additions round
+        let reference: f64 = data.iter().map(|&x| x as f64).sum();
+        let magnitude: f64 = data.iter().map(|&x| x.abs() as f64).sum();
+        let tolerance = f64::EPSILON * magnitude * (data.len() as f64).sqrt();
+        let sum = simd_sum_f64acc(&data);
+        prop_assert!((sum - reference).abs() <= tolerance, "{} vs reference {}", sum, reference);
+    }
+
+    #[test]
+    fn sum_and_sumsq_match_scalar(data in data()) {
+        let (sum, sumsq) = calculate_sum_and_sumsq_avx(&data);
+        // The sum can cancel, so its tolerance follows the mean's scaled up by the length
+        let sum_tol = f32_accumulator_tol(&data) * data.len() as f32;
+        prop_close(sum, data.iter().sum(), 0.0, sum_tol)?;
+        let rel_tol = 2.0 * f32::EPSILON * ((data.len() as f32).sqrt() + 1.0);
+        prop_close(sumsq, data.iter().map(|x| x * x).sum(), rel_tol, 0.0)?;
+    }
+
+    #[test]
+    fn dot_matches_f64_reference(data in data()) {
+        let other: Vec<f32> = data.iter().rev().map(|x| x * 0.5 - 1.0).collect();
+        let reference: f64 = data.iter().zip(&other).map(|(&a, &b)| a as f64 * b as f64).sum();
+        // Products of mixed sign cancel, so bound the error by the summed magnitudes
+        let magnitude: f64 = data.iter().zip(&other).map(|(&a, &b)| (a as f64 * b as f64).abs()).sum();
+        let abs_tol = 2.0 * f32::EPSILON * magnitude as f32 * ((data.len() as f32).sqrt() + 1.0);
+        prop_close(simd_dot(&data, &other).unwrap(), reference as f32, 0.0, abs_tol)?;
+    }
+
+    #[test]
+    fn skipnan_matches_filtered_scalar(data in data(), nans in prop::collection::vec(any::<prop::sample::Index>(), 0..5)) {
+        let mut data = data;
+        let len = data.len();
+        for nan in nans.iter().filter(|_| len > 0) {
+            data[nan.index(len)] = f32::NAN;
+        }
+        let finite: Vec<f32> = data.iter().copied().filter(|x| !x.is_nan()).collect();
+        match calculate_mean_skipnan(&data) {
+            Some(mean) => prop_close(mean, calculate_mean_scalar(&finite), 0.0, f32_accumulator_tol(&finite))?,
+            None => prop_assert!(finite.is_empty()),
+        }
+    }
+
+    #[test]
+    fn count_gt_matches_scalar(data in data(), threshold in -1.0e3f32..1.0e3) {
+        let expected = data.iter().filter(|&&x| x > threshold).count();
+        prop_assert_eq!(simd_count_gt(&data, threshold), expected);
+    }
+
+    #[test]
+    fn mean_where_gt_matches_filtered_scalar(data in data(), threshold in -1.0e3f32..1.0e3) {
+        let kept: Vec<f32> = data.iter().copied().filter(|&x| x > threshold).collect();
+        match mean_where_gt(&data, threshold) {
+            Some(mean) => prop_close(mean, calculate_mean_scalar(&kept), 0.0, f32_accumulator_tol(&kept))?,
+            None => prop_assert!(kept.is_empty()),
+        }
+    }
+
+    #[test]
+    fn sum_count_matches_sum_and_len(data in data()) {
+        let (sum, count) = simd_sum_count(&data);
+        prop_assert_eq!(count, data.len());
+        prop_assert_eq!(sum.to_bits(), simd_sum(&data).to_bits());
+        let reference: f64 = data.iter().map(|&x| x as f64).sum();
+        let tol = f32_accumulator_tol(&data) * data.len() as f32;
+        prop_close(sum, reference as f32, 0.0, tol)?;
+    }
+
+    #[test]
+    fn sum_abs_matches_f64_reference(data in data()) {
+        // Every term is non-negative, so the f32 accumulator's error is relative to the sum
+        let reference: f64 = data.iter().map(|&x| x.abs() as f64).sum();
+        let rel_tol = f32::EPSILON * ((data.len() as f32).sqrt() + 1.0);
+        prop_close(simd_sum_abs(&data), reference as f32, rel_tol, 0.0)?;
+    }
+
+    #[test]
+    fn sum_squares_matches_f64_reference(data in data()) {
+        // Squares round once in f32 before being summed, hence the extra epsilon
+        let reference: f64 = data.iter().map(|&x| (x as f64) * (x as f64)).sum();
+        let rel_tol = f32::EPSILON * ((data.len() as f32).sqrt() + 2.0);
+        prop_close(simd_sum_squares(&data), reference as f32, rel_tol, 0.0)?;
+    }
+
+    #[test]
+    fn f64_round_trip_is_exact(data in data()) {
+        let wide = simd_f32_to_f64_vec(&data);
+        prop_assert!(wide.iter().zip(&data).all(|(&w, &x)| w == x as f64));
+        prop_assert_eq!(simd_f64_to_f32_vec(&wide), data);
+    }
+
+    #[test]
+    fn merged_partials_match_whole(data in data(), split in any::<prop::sample::Index>()) {
+        let (left, right) = data.split_at(split.index(data.len() + 1));
+        let merged = Partial::from_slice(left).merge(Partial::from_slice(right));
+        prop_assert_eq!(merged.count, data.len());
+        match merged.finish() {
+            // f64 sums only round noticeably when the mean is converted to f32
+            Some(mean) => prop_close(mean, reference_mean(&data), f32::EPSILON, 1e-9)?,
+            None => prop_assert!(data.is_empty()),
+        }
+    }
+
+    #[test]
+    fn integer_means_are_exact(
+        ints in prop::collection::vec(any::<i32>(), 0..100),
+        bytes in prop::collection::vec(any::<u8>(), 0..200),
+    ) {
+        let sum: i64 = ints.iter().map(|&x| x as i64).sum();
+        prop_assert_eq!(calculate_mean_i32(&ints).to_bits(), (sum as f64 / ints.len() as f64).to_bits());
+        let sum: u64 = bytes.iter().map(|&x| x as u64).sum();
+        prop_assert_eq!(calculate_mean_u8(&bytes).to_bits(), (sum as f64 / bytes.len() as f64).to_bits());
+    }
+}
+
+#[test]
+fn empty_mean_is_none() {
+    assert_eq!(mean_scalar(&[]), None);
+    assert_eq!(mean_simd(&[]), None);
+    assert_eq!(mean_chunks(&[]), None);
+}
+
+#[test]
+fn single_element_mean_is_that_element() {
+    assert_eq!(mean_scalar(&[42.5]), Some(42.5));
+    assert_eq!(mean_simd(&[42.5]), Some(42.5));
+    assert_eq!(mean_chunks(&[42.5]), Some(42.5));
+}
+
+#[test]
+fn shorter_than_one_avx_chunk() {
+    // 7 elements never fill an 8-wide register, so only the remainder path runs
+    let data = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+    assert_eq!(mean_scalar(&data), Some(4.0));
+    assert_eq!(mean_simd(&data), Some(4.0));
+    assert_eq!(mean_chunks(&data), Some(4.0));
+}
+
+#[test]
+fn f64_mean_matches_naive_sum() {
+    for len in [1000, 1_000_003] {
+        let data: Vec<f64> = random_vec(len, 1);
+        let expected = data.iter().sum::<f64>() / len as f64;
+        let actual = calculate_mean_simd_f64(&data);
+        // f64 rounding over a million terms of magnitude 1e3 stays far below 1e-9
+        assert!((actual - expected).abs() <= 1e-9, "len {}: {} vs naive {}", len, actual, expected);
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn avx512_matches_scalar() {
+    if !is_x86_feature_detected!("avx512f") {
+        return;
+    }
+    let data: Vec<f32> = random_vec(1_000_000, 2);
+    let mean = unsafe { calculate_mean_simd_avx512(&data) };
+    assert_close(mean, calculate_mean_scalar(&data), 0.0, f32_accumulator_tol(&data));
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn sse_matches_scalar() {
+    if !is_x86_feature_detected!("sse2") {
+        return;
+    }
+    for len in [500, 50_000] {
+        let data: Vec<f32> = random_vec(len, 3);
+        let mean = unsafe { calculate_mean_simd_sse(&data) };
+        assert_close(mean, calculate_mean_scalar(&data), 0.0, f32_accumulator_tol(&data));
+    }
+}
+
+#[test]
+fn min_max_ignore_a_single_nan() {
+    // The NaN sits in the first AVX chunk; 11 elements also leave a scalar tail
+    let mut data: Vec<f32> = (1..=11).map(|x| x as f32).collect();
+    data[4] = f32::NAN;
+    assert_eq!(simd_min(&data), Some(1.0));
+    assert_eq!(simd_max(&data), Some(11.0));
+    assert!(simd_min(&[f32::NAN; 9]).unwrap().is_nan());
+    assert!(simd_max(&[f32::NAN; 9]).unwrap().is_nan());
+    assert_eq!(simd_min(&[]), None);
+}
+
+#[test]
+fn min_max_of_equal_values() {
+    assert_eq!(simd_min(&[2.5; 19]), Some(2.5));
+    assert_eq!(simd_max(&[2.5; 19]), Some(2.5));
+}
+
+#[test]
+fn kahan_keeps_small_terms_after_a_huge_one() {
+    // 1.0 is below half an ulp of 1e8 in f32, so a naive sum drops every one of them
+    let mut data = vec![1.0f32; 1_000_000];
+    data.insert(0, 1e8);
+    let reference = data.iter().map(|&x| x as f64).sum::<f64>() / data.len() as f64;
+    let naive_error = (calculate_mean_scalar(&data) as f64 - reference).abs();
+    let kahan_error = (calculate_mean_kahan(&data) as f64 - reference).abs();
+    assert!(kahan_error < naive_error, "kahan error {} vs naive {}", kahan_error, naive_error);
+    assert_close(calculate_mean_kahan(&data), reference as f32, f32::EPSILON, 0.0);
+}
+
+#[test]
+fn pairwise_keeps_adding_past_f32_precision() {
+    // A naive f32 sum of ones stalls at 2^24, where adding 1.0 no longer rounds up,
+    // while pairwise summation only ever adds partial sums of similar size
+    let data = vec![1.0f32; 20_000_000];
+    let naive_error = (calculate_mean_scalar(&data) - 1.0).abs();
+    let pairwise_error = (calculate_mean_pairwise(&data) - 1.0).abs();
+    assert!(naive_error > 0.1, "naive error {}", naive_error);
+    assert!(pairwise_error < naive_error, "pairwise error {} vs naive {}", pairwise_error, naive_error);
+    assert_eq!(calculate_mean_pairwise(&data), 1.0);
+}
+
+/// Check the f64-accumulator means are at least `ratio` times closer to an f64 reference than the f32 SIMD mean
+///
+/// The wide means only round once, when converting to f32, while the f32
+/// accumulators' error keeps growing with the length.
+fn assert_wide_accumulators_drift_less(len: usize, ratio: f64) {
+    // All-positive values, as in the benchmark, so the f32 error accumulates instead of cancelling
+    let data: Vec<f32> = random_vec::<f32>(len, 4).iter().map(|x| x.abs() / 10.0 + 20.0).collect();
+    let reference = data.iter().map(|&x| x as f64).sum::<f64>() / len as f64;
+    let f32_error = (calculate_mean_simd(&data) as f64 - reference).abs();
+    for wide in [calculate_mean_scalar_wide(&data), calculate_mean_simd_wide(&data)] {
+        let wide_error = (wide as f64 - reference).abs();
+        assert!(wide_error <= reference * f32::EPSILON as f64, "wide mean {} vs reference {}", wide, reference);
+        assert!(wide_error * ratio < f32_error, "wide error {} vs f32 error {}", wide_error, f32_error);
+    }
+}
+
+#[test]
+fn wide_accumulators_drift_less() {
+    assert_wide_accumulators_drift_less(4_000_000, 2.0);
+}
+
+#[test]
+#[ignore = "allocates 400 MB; run with --ignored"]
+fn wide_accumulators_drift_less_on_100m() {
+    assert_wide_accumulators_drift_less(100_000_000, 1e5);
+}
+
+#[test]
+fn chunks_are_generic_over_the_float_type() {
+    for len in [7, 8, 1001] {
+        let wide: Vec<f64> = random_vec(len, 5);
+        let expected = wide.iter().sum::<f64>() / len as f64;
+        assert!((calculate_mean_chunks::<f64>(&wide) - expected).abs() <= 1e-12, "len {}", len);
+
+        let narrow: Vec<f32> = random_vec(len, 5);
+        let mean = calculate_mean_chunks::<f32>(&narrow);
+        assert_close(mean, calculate_mean_scalar(&narrow), 0.0, f32_accumulator_tol(&narrow));
+    }
+}
+
+#[test]
+fn dot_of_lengths_off_the_lane_width() {
+    for len in [1, 7, 9, 13, 31] {
+        let a: Vec<f32> = (1..=len).map(|x| x as f32).collect();
+        let b = vec![2.0f32; len];
+        // 2 * (1 + ... + len), small integers that every summation order gets exactly
+        assert_eq!(simd_dot(&a, &b), Ok((len * (len + 1)) as f32), "len {}", len);
+    }
+    assert_eq!(simd_dot(&[], &[]), Ok(0.0));
+    assert_eq!(simd_dot(&[1.0; 3], &[1.0; 4]), Err(LengthMismatch { left: 3, right: 4 }));
+}
+
+#[test]
+fn skipnan_with_nan_and_infinity() {
+    // NaNs in both the AVX chunk and the tail are skipped
+    let data = [1.0, f32::NAN, 2.0, 3.0, f32::NAN, 4.0, 5.0, 6.0, f32::NAN, 9.0];
+    assert_eq!(calculate_mean_skipnan(&data), Some(30.0 / 7.0));
+
+    // Infinities are kept
+    let mut with_inf = data;
+    with_inf[3] = f32::INFINITY;
+    assert_eq!(calculate_mean_skipnan(&with_inf), Some(f32::INFINITY));
+    with_inf[9] = f32::NEG_INFINITY;
+    assert!(calculate_mean_skipnan(&with_inf).unwrap().is_nan());
+
+    assert_eq!(calculate_mean_skipnan(&[f32::NAN; 11]), None);
+    assert_eq!(calculate_mean_skipnan(&[]), None);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn avx512_masked_tails_match_scalar() {
+    if !is_x86_feature_detected!("avx512f") {
+        return;
+    }
+    // One full 16-float block, then every masked tail width from 0 to 15
+    for n in 16..=31 {
+        let data: Vec<f32> = random_vec(n, n as u64);
+        let mean = unsafe { calculate_mean_simd_avx512(&data) };
+        assert_close(mean, calculate_mean_scalar(&data), 0.0, f32_accumulator_tol(&data));
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_matches_sequential_simd() {
+    let data: Vec<f32> = random_vec(10_000_000, 6);
+    let mean = calculate_mean_parallel(&data);
+    assert_close(mean, calculate_mean_simd(&data), 0.0, f32_accumulator_tol(&data));
+    assert!(calculate_mean_parallel(&[]).is_nan());
+}
+
+#[test]
+fn count_gt_edge_cases() {
+    let data: Vec<f32> = (0..19).map(|x| x as f32).collect();
+    assert_eq!(simd_count_gt(&data, -1.0), 19);
+    assert_eq!(simd_count_gt(&data, 18.0), 0);
+    // Strictly greater: elements equal to the threshold don't count, in the chunks or the tail
+    assert_eq!(simd_count_gt(&data, 5.0), 13);
+    assert_eq!(simd_count_gt(&data, 17.0), 1);
+    assert_eq!(simd_count_gt(&[2.0; 11], 2.0), 0);
+    assert_eq!(simd_count_gt(&[f32::NAN; 11], 0.0), 0);
+}
+
+// `checked_mean` only cross-checks against the reference in debug builds
+#[cfg(debug_assertions)]
+#[test]
+fn checked_mean_accepts_non_finite_input() {
+    assert_eq!(checked_mean(&[f32::INFINITY]), f32::INFINITY);
+    assert_eq!(checked_mean(&[f32::NEG_INFINITY]), f32::NEG_INFINITY);
+    assert!(checked_mean(&[f32::NAN]).is_nan());
+    assert_eq!(checked_mean(&[f32::INFINITY; 9]), f32::INFINITY);
+}
+
+#[test]
+fn mean_iter_over_a_filtered_range() {
+    // The even numbers 0, 2, ..., 998 average to 499
+    let evens = (0..1000).filter(|i| i % 2 == 0).map(|i| i as f32);
+    assert_eq!(mean_iter(evens.clone()), Some(499.0));
+    assert_eq!(mean_iter(evens.clone()), mean_scalar(&evens.collect::<Vec<_>>()));
+    assert_eq!(mean_iter((0..1000).filter(|&i| i > 1000).map(|i| i as f32)), None);
+}
+
+#[test]
+fn clamped_mean_clips_extreme_outliers() {
+    let mut data: Vec<f32> = random_vec(1003, 7);
+    for i in (0..data.len()).step_by(97) {
+        data[i] = if i % 2 == 0 { 1e30 } else { -1e30 };
+    }
+    let clamped: Vec<f32> = data.iter().map(|x| x.clamp(-1000.0, 1000.0)).collect();
+    let expected = calculate_mean_scalar(&clamped);
+    let mean = clamped_mean(&data, -1000.0, 1000.0).unwrap();
+    assert_close(mean, expected, 0.0, f32_accumulator_tol(&clamped));
+    // Unclamped, the outliers dominate the mean
+    assert!(calculate_mean_scalar(&data).abs() > 1e20);
+}
+
+#[test]
+fn clamped_mean_rejects_invalid_bounds() {
+    let data = [1.0, 2.0, 3.0];
+    assert_eq!(clamped_mean(&data, 2.0, 2.0), Some(2.0));
+    assert_eq!(clamped_mean(&data, 3.0, 1.0), None);
+    assert_eq!(clamped_mean(&data, f32::NAN, 1.0), None);
+    assert_eq!(clamped_mean(&data, 0.0, f32::NAN), None);
+    assert_eq!(clamped_mean(&[], 0.0, 1.0), None);
+}
+
+#[test]
+fn partial_sums_a_long_shard_in_f64() {
+    // 0.1 is inexact in binary, so f32 lane sums round on nearly every add,
+    // while f64 ones stay exact to well below the f32 precision
+    let shard = vec![0.1f32; 1_000_000];
+    let partial = Partial::from_slice(&shard);
+    let exact = 0.1f32 as f64 * 1_000_000.0;
+    assert!((partial.sum - exact).abs() < 1e-6, "{} vs {}", partial.sum, exact);
+    assert_eq!(partial.count, shard.len());
+}
+
+#[test]
+fn out_of_range_index_is_an_error() {
+    let data = [1.0f32; 20];
+    // Inside the first 8-wide block, which the gather would otherwise read
+    let mut indices: Vec<u32> = (0..16).collect();
+    indices[3] = 20;
+    assert_eq!(mean_indexed(&data, &indices), Err(IndexOutOfBounds { index: 20, len: 20 }));
+}
+
+#[test]
+fn empty_index_list_is_none() {
+    assert_eq!(mean_indexed(&[1.0, 2.0], &[]), Ok(None));
+}
+
+#[test]
+fn nmse_edge_cases() {
+    assert_eq!(nmse(&[1.0, 2.0], &[1.0]), Err(LengthMismatch { left: 2, right: 1 }));
+    assert_eq!(nmse(&[], &[]), Ok(0.0));
+    assert_eq!(nmse(&[0.0; 9], &[0.0; 9]), Ok(0.0));
+    assert!(nmse(&[0.0; 9], &[1.0; 9]).unwrap().is_nan());
+    assert_eq!(nmse(&[2.0; 9], &[0.0; 9]), Ok(1.0));
+}
+
+#[test]
+fn l1_norm_matches_scalar() {
+    // 11 elements: one full AVX chunk of mixed signs plus a 3-element tail
+    let data = [1.5f32, -2.0, 3.25, -4.0, 0.0, -0.0, 7.0, -8.5, 9.0, -10.0, 11.75];
+    let expected: f32 = data.iter().map(|x| x.abs()).sum();
+    assert_close(simd_l1_norm(&data), expected, f32::EPSILON, 0.0);
+    assert_eq!(simd_l1_norm(&[-1.0; 11]), 11.0);
+    assert_eq!(simd_l1_norm(&[]), 0.0);
+}
@@ -0,0 +1,295 @@
+//! Checks of the variance, shape and other summary statistics against scalar references.
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand_distr::{Distribution, Normal};
+
+use simd_poc::{
+    LengthMismatch, RunningStats, calculate_std_simd, calculate_variance_simd, geometric_mean,
+    harmonic_mean, simd_correlation, simd_covariance, simd_kurtosis, simd_l2_norm, simd_rms,
+    simd_skewness, simd_summary, variance_one_pass, variance_two_pass, weighted_mean,
+};
+
+mod common;
+
+use common::{assert_close, reference_mean};
+
+/// `len` seeded samples of a normal distribution
+fn normal_vec(len: usize, mean: f32, std_dev: f32, seed: u64) -> Vec<f32> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(mean, std_dev).unwrap();
+    (0..len).map(|_| normal.sample(&mut rng)).collect()
+}
+
+/// Population variance by the scalar two-pass method
+fn variance_reference(data: &[f32]) -> f32 {
+    let mean = data.iter().sum::<f32>() / data.len() as f32;
+    data.iter().map(|&x| (x - mean) * (x - mean)).sum::<f32>() / data.len() as f32
+}
+
+#[test]
+fn variance_matches_two_pass_scalar() {
+    for len in [7, 1000, 100_003] {
+        let data = normal_vec(len, 50.0, 10.0, 0);
+        let expected = variance_reference(&data);
+        assert_close(calculate_variance_simd(&data), expected, 1e-3, 0.0);
+        assert_close(calculate_std_simd(&data), expected.sqrt(), 1e-3, 0.0);
+    }
+}
+
+/// Check every `simd_summary` field against independent scalar computations in f64
+fn assert_summary_matches_scalar(data: &[f32]) {
+    let summary = simd_summary(data).unwrap();
+    let n = data.len() as f64;
+    let mean = data.iter().map(|&x| x as f64).sum::<f64>() / n;
+    let variance = data.iter().map(|&x| (x as f64 - mean).powi(2)).sum::<f64>() / n;
+    assert_eq!(summary.count, data.len());
+    assert_close(summary.mean, mean as f32, 2.0 * f32::EPSILON, 0.0);
+    assert_close(summary.variance, variance as f32, 1e-5, 0.0);
+    assert_eq!(summary.min, data.iter().copied().fold(f32::NAN, f32::min));
+    assert_eq!(summary.max, data.iter().copied().fold(f32::NAN, f32::max));
+}
+
+#[test]
+fn summary_matches_scalar() {
+    for len in [1, 7, 8, 1000, 100_003] {
+        assert_summary_matches_scalar(&normal_vec(len, 0.0, 1.0, 1));
+    }
+}
+
+#[test]
+fn summary_of_offset_data_does_not_cancel() {
+    // A mean 10^4 times the spread wipes out an f32 one-pass variance
+    assert_summary_matches_scalar(&normal_vec(100_003, 1e4, 1.0, 2));
+
+    // A large first element followed by a wide spread, so the shift is far from the mean
+    let mut data = normal_vec(100_003, 0.0, 1e3, 3);
+    data[0] = 1e6;
+    assert_summary_matches_scalar(&data);
+}
+
+#[test]
+fn summary_with_a_leading_non_finite_value() {
+    let mut data = normal_vec(17, 5.0, 1.0, 4);
+    data[0] = f32::NAN;
+    let summary = simd_summary(&data).unwrap();
+    assert!(summary.mean.is_nan());
+    assert_eq!(summary.max, data[1..].iter().copied().fold(f32::MIN, f32::max));
+
+    // An infinite max is legitimate: the mean is infinite, but the min stays finite
+    data[0] = f32::INFINITY;
+    let summary = simd_summary(&data).unwrap();
+    assert_eq!(summary.mean, f32::INFINITY);
+    assert_eq!(summary.max, f32::INFINITY);
+    assert_eq!(summary.min, data[1..].iter().copied().fold(f32::MAX, f32::min));
+    assert!(summary.variance.is_nan());
+
+    assert_eq!(simd_summary(&[]), None);
+}
+
+/// `RunningStats` fed one value at a time
+fn pushed(data: &[f32]) -> RunningStats {
+    let mut stats = RunningStats::new();
+    for &x in data {
+        stats.push(x);
+    }
+    stats
+}
+
+#[test]
+fn running_stats_push_and_extend_agree() {
+    // Offset data, where rounding the batch to f32 statistics would show
+    for data in [normal_vec(13, 0.0, 1.0, 5), normal_vec(100_003, 1e4, 1.0, 6)] {
+        let one_at_a_time = pushed(&data);
+
+        let mut bulk = RunningStats::new();
+        bulk.extend(&data);
+
+        let mut chunked = RunningStats::new();
+        for chunk in data.chunks(1000) {
+            chunked.extend(chunk);
+        }
+
+        for stats in [bulk, chunked] {
+            assert_eq!(stats.count(), one_at_a_time.count());
+            assert_close(stats.mean().unwrap(), one_at_a_time.mean().unwrap(), f32::EPSILON, 0.0);
+            assert_close(stats.variance().unwrap(), one_at_a_time.variance().unwrap(), 1e-6, 0.0);
+        }
+    }
+}
+
+#[test]
+fn running_stats_mixes_push_and_extend() {
+    let data = normal_vec(1000, 3.0, 2.0, 7);
+    let mut mixed = RunningStats::new();
+    mixed.extend(&data[..400]);
+    for &x in &data[400..410] {
+        mixed.push(x);
+    }
+    mixed.extend(&data[410..]);
+    mixed.extend(&[]);
+
+    let expected = pushed(&data);
+    assert_close(mixed.mean().unwrap(), expected.mean().unwrap(), f32::EPSILON, 0.0);
+    assert_close(mixed.variance().unwrap(), expected.variance().unwrap(), 1e-6, 0.0);
+    assert_eq!(RunningStats::new().mean(), None);
+}
+
+#[test]
+fn l2_norm_and_rms_match_scalar() {
+    for len in [7, 1000, 100_003] {
+        let data = normal_vec(len, 0.0, 10.0, 8);
+        let sumsq = data.iter().map(|&x| x as f64 * x as f64).sum::<f64>();
+        let rel_tol = f32::EPSILON * ((len as f32).sqrt() + 2.0);
+        assert_close(simd_l2_norm(&data), sumsq.sqrt() as f32, rel_tol, 0.0);
+        assert_close(simd_rms(&data).unwrap(), (sumsq / len as f64).sqrt() as f32, rel_tol, 0.0);
+    }
+    assert_eq!(simd_l2_norm(&[]), 0.0);
+    assert_eq!(simd_rms(&[]), None);
+}
+
+#[test]
+fn sixteen_threes() {
+    let data = [3.0f32; 16];
+    assert_eq!(simd_rms(&data), Some(3.0));
+    assert_eq!(simd_l2_norm(&data), 12.0);
+    assert_eq!(calculate_variance_simd(&data), 0.0);
+}
+
+#[test]
+fn geometric_mean_of_powers_of_two() {
+    // (1 * 2 * 4 * 8)^(1/4) = 2^1.5
+    let mean = geometric_mean(&[1.0, 2.0, 4.0, 8.0]).unwrap();
+    assert_close(mean, 2.0f32.powf(1.5), 2.0 * f32::EPSILON, 0.0);
+    assert_eq!(geometric_mean(&[5.0]), Some(5.0));
+}
+
+#[test]
+fn geometric_mean_of_non_positive_input_is_none() {
+    assert_eq!(geometric_mean(&[1.0, 0.0, 4.0]), None);
+    assert_eq!(geometric_mean(&[1.0, -2.0, 4.0]), None);
+    assert_eq!(geometric_mean(&[]), None);
+}
+
+#[test]
+fn harmonic_mean_of_one_two_four() {
+    // 3 / (1 + 1/2 + 1/4) = 12/7
+    let mean = harmonic_mean(&[1.0, 2.0, 4.0]).unwrap();
+    assert_close(mean, 12.0 / 7.0, 2.0 * f32::EPSILON, 0.0);
+
+    // Repeating the set leaves the mean unchanged and exercises the AVX chunks
+    let repeated: Vec<f32> = [1.0, 2.0, 4.0].repeat(7);
+    assert_close(harmonic_mean(&repeated).unwrap(), 12.0 / 7.0, 4.0 * f32::EPSILON, 0.0);
+}
+
+#[test]
+fn harmonic_mean_with_a_zero_is_none() {
+    assert_eq!(harmonic_mean(&[1.0, 0.0, 4.0]), None);
+    assert_eq!(harmonic_mean(&[]), None);
+}
+
+// 11 pairs, so the AVX chunk and the scalar tail both contribute
+const PAIRED_A: [f32; 11] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0];
+const PAIRED_B: [f32; 11] = [2.5, 4.0, 5.0, 4.0, 5.5, 7.0, 8.0, 9.0, 10.0, 12.0, 11.0];
+
+#[test]
+fn covariance_and_correlation_of_a_known_dataset() {
+    // Reference values from numpy: np.cov(a, b, bias=True)[0, 1] and np.corrcoef(a, b)[0, 1]
+    assert_close(simd_covariance(&PAIRED_A, &PAIRED_B).unwrap(), 9.272727, 1e-6, 0.0);
+    assert_close(simd_correlation(&PAIRED_A, &PAIRED_B).unwrap(), 0.975418, 1e-6, 0.0);
+
+    // Shifting both samples changes neither
+    let a: Vec<f32> = PAIRED_A.iter().map(|x| x + 1000.0).collect();
+    let b: Vec<f32> = PAIRED_B.iter().map(|x| x - 1000.0).collect();
+    assert_close(simd_covariance(&a, &b).unwrap(), 9.272727, 1e-5, 0.0);
+}
+
+#[test]
+fn correlation_with_itself_is_one() {
+    let data = normal_vec(1000, 5.0, 2.0, 9);
+    assert_close(simd_correlation(&data, &data).unwrap(), 1.0, 1e-5, 0.0);
+    let variance = simd_covariance(&data, &data).unwrap();
+    assert_close(variance, calculate_variance_simd(&data), 1e-4, 0.0);
+}
+
+#[test]
+fn covariance_rejects_mismatched_lengths() {
+    assert_eq!(simd_covariance(&[1.0; 3], &[1.0; 2]), Err(LengthMismatch { left: 3, right: 2 }));
+    assert_eq!(simd_correlation(&[1.0; 3], &[1.0; 2]), Err(LengthMismatch { left: 3, right: 2 }));
+}
+
+#[test]
+fn one_pass_variance_cancels_on_offset_data() {
+    // E[x²] and E[x]² are both ~1e8 and agree in every digit f32 keeps, so
+    // their difference is rounding noise rather than the variance of 1
+    let data = normal_vec(20_000, 1e4, 1.0, 10);
+    let mean = data.iter().map(|&x| x as f64).sum::<f64>() / data.len() as f64;
+    let reference = (data.iter().map(|&x| (x as f64 - mean).powi(2)).sum::<f64>() / data.len() as f64) as f32;
+
+    assert_close(variance_two_pass(&data), reference, 1e-3, 0.0);
+    let one_pass = variance_one_pass(&data);
+    assert!((one_pass - reference).abs() > 0.5 * reference, "one-pass {} vs {}", one_pass, reference);
+}
+
+#[test]
+fn symmetric_data_has_zero_skewness() {
+    let data: Vec<f32> = (1..=9).map(|x| x as f32).collect();
+    assert_close(simd_skewness(&data).unwrap(), 0.0, 0.0, 1e-6);
+    assert_close(simd_kurtosis(&data).unwrap(), -1.23, 1e-5, 0.0);
+}
+
+#[test]
+fn skewness_and_kurtosis_of_a_right_skewed_dataset() {
+    // m2 = 4, m3 = 12, m4 = 52 around a mean of 2
+    let data = [1.0, 1.0, 1.0, 1.0, 6.0, 1.0, 1.0, 1.0, 1.0, 6.0];
+    assert_close(simd_skewness(&data).unwrap(), 1.5, 1e-5, 0.0);
+    assert_close(simd_kurtosis(&data).unwrap(), 0.25, 1e-5, 0.0);
+}
+
+#[test]
+fn skewness_and_kurtosis_of_constant_data_are_none() {
+    for data in [&[][..], &[4.0], &[3.0; 16]] {
+        assert_eq!(simd_skewness(data), None);
+        assert_eq!(simd_kurtosis(data), None);
+    }
+}
+
+#[test]
+fn weighted_mean_of_a_hand_computed_example() {
+    // (1*1 + 2*2 + 3*3 + 4*4) / (1 + 2 + 3 + 4) = 30 / 10
+    let values = [1.0, 2.0, 3.0, 4.0];
+    assert_eq!(weighted_mean(&values, &values), Ok(Some(3.0)));
+
+    // Longer than one AVX chunk, with most weights zero: (2 * 9 + 1 * 3) / 12
+    let mut values = vec![2.0; 9];
+    values.extend([1.0; 3]);
+    let mut weights = vec![0.0; 9];
+    weights[0] = 9.0;
+    weights.extend([1.0; 3]);
+    assert_close(weighted_mean(&values, &weights).unwrap().unwrap(), 21.0 / 12.0, 1e-6, 0.0);
+}
+
+#[test]
+fn equal_weights_give_the_plain_mean() {
+    for len in [1, 7, 8, 1000, 100_003] {
+        let data = normal_vec(len, 50.0, 10.0, 11);
+        let weights = vec![0.5; len];
+        let expected = reference_mean(&data);
+        assert_close(weighted_mean(&data, &weights).unwrap().unwrap(), expected, 1e-5, 0.0);
+    }
+}
+
+#[test]
+fn weighted_mean_with_zero_total_weight_is_none() {
+    assert_eq!(weighted_mean(&[], &[]), Ok(None));
+    assert_eq!(weighted_mean(&[1.0, 2.0], &[0.0, 0.0]), Ok(None));
+    assert_eq!(weighted_mean(&[1.0, 2.0], &[1.0, -1.0]), Ok(None));
+}
+
+#[test]
+fn weighted_mean_rejects_mismatched_lengths() {
+    assert_eq!(
+        weighted_mean(&[1.0, 2.0, 3.0], &[1.0, 1.0]),
+        Err(LengthMismatch { left: 3, right: 2 })
+    );
+}
@@ -0,0 +1,19 @@
+//! The JavaScript exports called as plain Rust functions.
+//!
+//! `#[wasm_bindgen]` leaves the functions callable natively, so this checks
+//! they forward to the right means without needing a wasm runtime.
+
+use simd_poc::{calculate_mean_scalar, calculate_mean_simd, mean_scalar_js, mean_simd_js};
+
+#[test]
+fn exports_match_the_means() {
+    let data: Vec<f32> = (0..1001).map(|i| i as f32 * 0.5 - 100.0).collect();
+    assert_eq!(mean_scalar_js(&data).to_bits(), calculate_mean_scalar(&data).to_bits());
+    assert_eq!(mean_simd_js(&data).to_bits(), calculate_mean_simd(&data).to_bits());
+}
+
+#[test]
+fn empty_array_is_nan() {
+    assert!(mean_scalar_js(&[]).is_nan());
+    assert!(mean_simd_js(&[]).is_nan());
+}